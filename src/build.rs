@@ -0,0 +1,232 @@
+//! Helpers for compiling DBML files from a build script (`build.rs`).
+//!
+//! ```no_run
+//! // build.rs
+//! sea_orm_dbml::build::Builder::new()
+//!   .input("schema/bakery.dbml")
+//!   .compile()
+//!   .unwrap();
+//! ```
+//!
+//! The settings and inputs can also be loaded from the project configuration file.
+//!
+//! ```no_run
+//! // build.rs
+//! sea_orm_dbml::build::Builder::from_config_file("sea-orm-dbml.toml")
+//!   .unwrap()
+//!   .compile()
+//!   .unwrap();
+//! ```
+//!
+//! ```ignore
+//! // src/entities.rs
+//! sea_orm_dbml::include_entities!("bakery");
+//! ```
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::compiler::backend::Backend;
+use crate::compiler::config::*;
+use crate::compiler::err::*;
+use crate::compile_output;
+
+/// Includes the entities generated by [`Builder`] for the DBML file with the given file stem.
+#[macro_export]
+macro_rules! include_entities {
+  ($name:literal) => {
+    include!(concat!(env!("OUT_DIR"), "/", $name, ".rs"));
+  };
+}
+
+/// Compiles DBML files into `OUT_DIR` and tells Cargo to rerun the build script when they change.
+#[derive(Debug, Clone)]
+pub struct Builder {
+  inputs: Vec<PathBuf>,
+  out_dir: Option<PathBuf>,
+  config: Config,
+  config_path: Option<PathBuf>,
+}
+
+impl Default for Builder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Builder {
+  pub fn new() -> Self {
+    Self {
+      inputs: vec![],
+      out_dir: None,
+      config: Config::default(),
+      config_path: None,
+    }
+  }
+
+  /// Creates a builder from the inputs and settings of a configuration file.
+  /// The outputs of the file are ignored, since the entities are always written into the output directory.
+  pub fn from_config_file(path: impl AsRef<Path>) -> Result<Self, CompilerError> {
+    let path = path.as_ref();
+    let file = ConfigFile::read(path)?;
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+
+    Ok(Self {
+      inputs: file
+        .inputs
+        .iter()
+        .map(|input| base_dir.join(&input.path))
+        .collect(),
+      out_dir: None,
+      config: file.apply(Config::default())?,
+      config_path: Some(path.to_path_buf()),
+    })
+  }
+
+  /// Adds a DBML file to compile.
+  pub fn input(mut self, path: impl AsRef<Path>) -> Self {
+    self.inputs.push(path.as_ref().to_path_buf());
+
+    self
+  }
+
+  /// Sets the output directory. The default output directory is `OUT_DIR`.
+  pub fn out_dir(mut self, path: impl AsRef<Path>) -> Self {
+    self.out_dir = Some(path.as_ref().to_path_buf());
+
+    self
+  }
+
+  /// Sets the code generator. The default backend generates SeaORM entities.
+  pub fn backend(mut self, backend: impl Backend + 'static) -> Self {
+    self.config.backend = Some(Arc::new(backend));
+
+    self
+  }
+
+  /// Sets the database entity target.
+  pub fn target(mut self, target: Target) -> Self {
+    self.config.target = Some(target);
+
+    self
+  }
+
+  /// Sets the enum type for storing a value.
+  pub fn enum_type(mut self, enum_type: EnumType) -> Self {
+    self.config.enum_type = enum_type;
+
+    self
+  }
+
+  /// Enables or disables native enum for the database.
+  pub fn native_enum(mut self, is_native_enum: bool) -> Self {
+    self.config.is_native_enum = is_native_enum;
+
+    self
+  }
+
+  /// Enables or disables the primary key newtypes.
+  pub fn pk_newtype(mut self, is_pk_newtype: bool) -> Self {
+    self.config.is_pk_newtype = is_pk_newtype;
+
+    self
+  }
+
+  /// Sets the naming rules of the modules and types of the tables.
+  pub fn naming(mut self, naming: Naming) -> Self {
+    self.config.naming = naming;
+
+    self
+  }
+
+  /// Sets the SeaORM release the code is generated for.
+  pub fn sea_orm_version(mut self, sea_orm_version: SeaOrmVersion) -> Self {
+    self.config.sea_orm_version = sea_orm_version;
+
+    self
+  }
+
+  /// Sets the crate of the date and time types.
+  pub fn date_time_crate(mut self, date_time_crate: DateTimeCrate) -> Self {
+    self.config.date_time_crate = date_time_crate;
+
+    self
+  }
+
+  /// Sets the crate of the decimal type.
+  pub fn decimal_crate(mut self, decimal_crate: DecimalCrate) -> Self {
+    self.config.decimal_crate = decimal_crate;
+
+    self
+  }
+
+  /// Maps the columns of a DBML type name (e.g. `citext` or `varchar(320)`) to the given types.
+  pub fn type_override(mut self, type_name: impl ToString, type_override: TypeOverride) -> Self {
+    self
+      .config
+      .type_overrides
+      .insert(type_name.to_string(), type_override);
+
+    self
+  }
+
+  /// Compiles every input into `<out_dir>/<input file stem>.rs` and returns the written paths.
+  /// The warnings are forwarded to Cargo as `cargo:warning`.
+  pub fn compile(self) -> Result<Vec<PathBuf>, CompilerError> {
+    let out_dir = match self.out_dir.or_else(|| env::var_os("OUT_DIR").map(PathBuf::from)) {
+      Some(out_dir) => out_dir,
+      None => {
+        return Err(CompilerError::Config {
+          message: "OUT_DIR is not set".to_string(),
+        })
+      }
+    };
+
+    if let Some(config_path) = &self.config_path {
+      println!("cargo:rerun-if-changed={}", config_path.display());
+    }
+
+    // the builder always uses the single-file output mode
+    let config = Config {
+      output_mode: OutputMode::File,
+      ..self.config
+    };
+
+    self
+      .inputs
+      .iter()
+      .map(|in_path| {
+        println!("cargo:rerun-if-changed={}", in_path.display());
+
+        let file_stem = in_path.file_stem().ok_or_else(|| CompilerError::Config {
+          message: format!("'{}' is not a file path", in_path.display()),
+        })?;
+        let out_path = out_dir.join(file_stem).with_extension("rs");
+
+        let raw = fs::read_to_string(in_path)?;
+        let output = compile_output(&raw, &config)?;
+
+        for warning in output.warnings.iter() {
+          println!("cargo:warning={}: {}", in_path.display(), warning);
+        }
+
+        fs::write(&out_path, strip_inner_doc(output.code().unwrap_or_default()))?;
+
+        Ok(out_path)
+      })
+      .collect()
+  }
+}
+
+/// Turns the inner doc comments (`//!`) into plain comments, since `include!` does not accept inner attributes.
+fn strip_inner_doc(code: &str) -> String {
+  code
+    .lines()
+    .map(|line| match line.strip_prefix("//!") {
+      Some(rest) => format!("//{}\n", rest),
+      None => format!("{}\n", line),
+    })
+    .collect()
+}
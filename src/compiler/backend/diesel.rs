@@ -0,0 +1,471 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::compiler::config::*;
+use crate::compiler::err::*;
+use crate::compiler::traits::*;
+use crate::compiler::warn::*;
+use crate::compiler::{ref_location, resolve_ref_ident, Output, OutputFile};
+use crate::generator::Block;
+use crate::DEFAULT_SCHEMA;
+
+use inflector::Inflector;
+
+use dbml_rs::*;
+
+use super::{check_array_dimensions, gen_header, is_native_enum, Backend};
+
+/// Backend generating the Diesel schema (`diesel::table!`) along with the model structs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Diesel;
+
+impl Backend for Diesel {
+  fn name(&self) -> &str {
+    "diesel"
+  }
+
+  fn generate(
+    &self,
+    ast: &analyzer::SemanticSchemaBlock,
+    config: &Config,
+  ) -> Result<Output, CompilerError> {
+    check_array_dimensions(ast, config)?;
+
+    // Diesel identifies the rows by the primary key, so the tables without one are left out
+    let (tables, skipped): (Vec<_>, Vec<_>) = ast
+      .tables
+      .iter()
+      .partition(|table| !table.meta_indexer.pk_list.is_empty());
+
+    let warnings = skipped
+      .iter()
+      .map(|table| {
+        CompilerWarning::new(
+          WarningCode::MissingPrimaryKey,
+          format!("table '{}' has no primary key and is ignored", table.ident.name),
+          Location::table(table),
+        )
+      })
+      .collect();
+
+    let files = match config.output_mode {
+      OutputMode::File => {
+        let codegen = gen_header(ast)
+          .line_skip(1)
+          .block(gen_schema(ast, &tables, config, 1)?)
+          .line_skip(1)
+          .block(gen_models(ast, &tables, config, 1)?);
+
+        vec![OutputFile {
+          path: PathBuf::new(),
+          code: codegen.to_string(),
+        }]
+      }
+      OutputMode::Directory => {
+        let mod_codegen = gen_header(ast)
+          .line_skip(1)
+          .line("pub mod models;")
+          .line("pub mod schema;");
+
+        vec![
+          OutputFile {
+            path: PathBuf::from("mod.rs"),
+            code: mod_codegen.to_string(),
+          },
+          OutputFile {
+            path: PathBuf::from("schema.rs"),
+            code: gen_header(ast)
+              .line_skip(1)
+              .block(gen_schema(ast, &tables, config, 0)?)
+              .to_string(),
+          },
+          OutputFile {
+            path: PathBuf::from("models.rs"),
+            code: gen_header(ast)
+              .line_skip(1)
+              .block(gen_models(ast, &tables, config, 0)?)
+              .to_string(),
+          },
+        ]
+      }
+    };
+
+    Ok(Output { files, warnings })
+  }
+}
+
+/// Generates the `diesel::table!` of every table, followed by the `joinable!` of the refs.
+/// The schema is wrapped in a `pub mod schema` block unless `level` is zero (i.e. a file of its own).
+fn gen_schema(
+  ast: &analyzer::SemanticSchemaBlock,
+  tables: &[&ast::table::TableBlock],
+  config: &Config,
+  level: usize,
+) -> Result<Block, CompilerError> {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+
+  let mod_block = match level {
+    0 => Block::new(0, None::<String>),
+    _ => Block::new(level, Some("pub mod schema")),
+  };
+
+  // enum sql types
+  let mod_block = match is_native_enum(config) && !ast.enums.is_empty() {
+    true => {
+      let types_block = ast.enums.iter().enumerate().fold(
+        Block::new(level + 1, Some("pub mod sql_types")),
+        |acc, (i, r#enum)| {
+          let ast::enums::EnumIdent { name, schema } = &r#enum.ident;
+
+          let sql_type = match (&target, schema) {
+            (Target::Postgres, Some(schema)) if schema != DEFAULT_SCHEMA => {
+              format!(r#"postgres_type(name = "{}", schema = "{}")"#, name, schema)
+            }
+            (Target::Postgres, _) => format!(r#"postgres_type(name = "{}")"#, name),
+            _ => r#"mysql_type(name = "Enum")"#.to_string(),
+          };
+
+          acc
+            .line_skip(usize::from(i > 0))
+            .line("#[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]")
+            .line(format!("#[diesel({})]", sql_type))
+            .line(format!("pub struct {};", name.to_pascal_case()))
+        },
+      );
+
+      mod_block.block(types_block)
+    }
+    false => mod_block,
+  };
+
+  // table listing
+  let has_types = is_native_enum(config) && !ast.enums.is_empty();
+  let mod_block = tables.iter().enumerate().try_fold(mod_block, |acc, (i, table)| {
+    Ok::<_, CompilerError>(
+      acc
+        .line_skip(usize::from(i > 0 || has_types))
+        .block(gen_table(table, config, level)?),
+    )
+  })?;
+
+  // join listing
+  let mut joined = HashSet::new();
+  let mut joinable_lines = vec![];
+
+  for ref_block in ast.refs.iter() {
+    if !matches!(
+      ref_block.rel,
+      ast::refs::Relation::Many2One | ast::refs::Relation::One2One
+    ) {
+      continue;
+    }
+
+    let location = ref_location(ast, &ref_block.lhs);
+    let lhs = resolve_ref_ident(ast, &ref_block.lhs, &location)?;
+    let rhs = resolve_ref_ident(ast, &ref_block.rhs, &location)?;
+
+    let parent = tables.iter().find(|table| table.ident.name == rhs.table);
+    let has_child = tables.iter().any(|table| table.ident.name == lhs.table);
+
+    // a join always goes through the primary key of the parent, and only once for each pair of tables
+    let is_joinable = match parent {
+      Some(parent) => has_child && lhs.table != rhs.table && parent.meta_indexer.pk_list == rhs.compositions[..1],
+      None => false,
+    };
+
+    let mut pair = [lhs.table.clone(), rhs.table.clone()];
+    pair.sort();
+
+    if is_joinable && joined.insert(pair) {
+      joinable_lines.push(format!(
+        "diesel::joinable!({} -> {} ({}));",
+        lhs.table.to_snake_case(),
+        rhs.table.to_snake_case(),
+        lhs.compositions[0].to_snake_case()
+      ))
+    }
+  }
+
+  let mod_block = joinable_lines
+    .iter()
+    .fold(mod_block.line_skip(usize::from(!joinable_lines.is_empty())), |acc, line| acc.line(line));
+
+  let table_names: Vec<_> = tables
+    .iter()
+    .map(|table| table.ident.name.to_snake_case())
+    .collect();
+
+  Ok(mod_block.line_skip(usize::from(tables.len() > 1)).line_cond(
+    tables.len() > 1,
+    format!(
+      "diesel::allow_tables_to_appear_in_same_query!({});",
+      table_names.join(", ")
+    ),
+  ))
+}
+
+/// Generates the `diesel::table!` of a table.
+fn gen_table(
+  table: &ast::table::TableBlock,
+  config: &Config,
+  level: usize,
+) -> Result<Block, CompilerError> {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+  let ident = &table.ident;
+
+  let name = match (target.has_schema(), &ident.schema) {
+    (true, Some(schema)) if schema != DEFAULT_SCHEMA => {
+      format!("{}.{}", schema, ident.name.to_snake_case())
+    }
+    _ => ident.name.to_snake_case(),
+  };
+  let pk_names: Vec<_> = table
+    .meta_indexer
+    .pk_list
+    .iter()
+    .map(|name| name.to_snake_case())
+    .collect();
+
+  let cols_block = Block::new(level + 2, Some(format!("{} ({})", name, pk_names.join(", "))));
+
+  let cols_block = table.cols.iter().try_fold(cols_block, |acc, col| {
+    let sql_type = col
+      .r#type
+      .to_diesel_type(config)
+      .map_err(|err| err.at(Location::column(ident, col)))?;
+    let sql_type = match col.settings.is_nullable {
+      true => format!("Nullable<{}>", sql_type),
+      false => sql_type,
+    };
+    let col_name = col.name.to_snake_case();
+
+    Ok::<_, CompilerError>(
+      acc
+        .line_cond(
+          col_name != col.name,
+          format!(r#"#[sql_name = "{}"]"#, col.name),
+        )
+        .line(format!("{} -> {},", col_name, sql_type)),
+    )
+  })?;
+
+  let enum_names: Vec<_> = match is_native_enum(config) {
+    true => table
+      .cols
+      .iter()
+      .filter_map(|col| match &col.r#type.type_name {
+        ast::table::ColumnTypeName::Enum(name) => Some(name.to_pascal_case()),
+        _ => None,
+      })
+      .fold(vec![], |mut acc, name| {
+        if !acc.contains(&name) {
+          acc.push(name)
+        }
+
+        acc
+      }),
+    false => vec![],
+  };
+
+  let table_block = Block::new(level + 1, Some("diesel::table!")).line("use diesel::sql_types::*;");
+  let table_block = enum_names
+    .iter()
+    .fold(table_block, |acc, name| acc.line(format!("use super::sql_types::{};", name)))
+    .line_skip(1)
+    .line_cond(
+      ident.name.to_snake_case() != ident.name,
+      format!(r#"#[sql_name = "{}"]"#, ident.name),
+    )
+    .block(cols_block);
+
+  Ok(table_block)
+}
+
+/// Generates the `Queryable`, `Selectable` and `Insertable` struct of every table, along with the native enums.
+/// The models are wrapped in a `pub mod models` block unless `level` is zero (i.e. a file of its own).
+fn gen_models(
+  ast: &analyzer::SemanticSchemaBlock,
+  tables: &[&ast::table::TableBlock],
+  config: &Config,
+  level: usize,
+) -> Result<Block, CompilerError> {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+
+  let backend = match target {
+    Target::MySql => "diesel::mysql::Mysql",
+    Target::Postgres => "diesel::pg::Pg",
+    Target::Sqlite => "diesel::sqlite::Sqlite",
+  };
+
+  let mod_block = match level {
+    0 => Block::new(0, None::<String>),
+    _ => Block::new(level, Some("pub mod models")),
+  };
+
+  let mod_block = tables.iter().enumerate().try_fold(mod_block, |acc, (i, table)| {
+    let ident = &table.ident;
+
+    let struct_block = Block::new(level + 1, Some(format!("pub struct {}", config.naming.table_name(&ident.name).to_class_case())));
+
+    let struct_block = table.cols.iter().try_fold(struct_block, |acc, col| {
+      // the type is mapped even for enums, so that the unsupported types are reported alike
+      let rust_type = col
+        .r#type
+        .to_rust_type(config)
+        .map_err(|err| err.at(Location::column(ident, col)))?;
+
+      let rust_type = match &col.r#type.type_name {
+        ast::table::ColumnTypeName::Enum(name) => {
+          let enum_type = match config.enum_type {
+            _ if is_native_enum(config) => name.to_pascal_case(),
+            EnumType::String(_) => "String".to_string(),
+            EnumType::Integer => "i32".to_string(),
+          };
+
+          array_rust_type(enum_type, &col.r#type.arrays)
+        }
+        type_name => match to_pg_model_type(type_name) {
+          Some(model_type) => array_rust_type(model_type.to_string(), &col.r#type.arrays),
+          None => to_model_type(&rust_type),
+        },
+      };
+      let rust_type = annotated_rust_type(col).unwrap_or(rust_type);
+      let rust_type = match col.settings.is_nullable {
+        true => format!("Option<{}>", rust_type),
+        false => rust_type,
+      };
+
+      Ok::<_, CompilerError>(acc.line(format!("pub {}: {},", col.name.to_snake_case(), rust_type)))
+    })?;
+
+    Ok::<_, CompilerError>(
+      acc
+        .line_skip(usize::from(i > 0))
+        .line("#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable)]")
+        .line(format!(
+          "#[diesel(table_name = super::schema::{})]",
+          ident.name.to_snake_case()
+        ))
+        .line(format!("#[diesel(check_for_backend({}))]", backend))
+        .block(struct_block),
+    )
+  })?;
+
+  if !is_native_enum(config) {
+    return Ok(mod_block);
+  }
+
+  let value_type = match target {
+    Target::MySql => "diesel::mysql::MysqlValue<'_>",
+    _ => "diesel::pg::PgValue<'_>",
+  };
+
+  let mod_block = ast.enums.iter().fold(mod_block, |acc, r#enum| {
+    let name = r#enum.ident.name.to_pascal_case();
+    let sql_type = format!("super::schema::sql_types::{}", name);
+
+    let enum_block = r#enum.values.iter().fold(
+      Block::new(level + 1, Some(format!("pub enum {}", name))),
+      |acc, value| acc.line(format!("{},", value.value.to_pascal_case())),
+    );
+
+    let to_sql_block = Block::new(
+      level + 2,
+      Some(format!(
+        "fn to_sql<'b>(&'b self, out: &mut diesel::serialize::Output<'b, '_, {}>) -> diesel::serialize::Result",
+        backend
+      )),
+    )
+    .line("let value = match self {");
+    let to_sql_block = r#enum
+      .values
+      .iter()
+      .fold(to_sql_block, |acc, value| {
+        acc.line(format!(
+          "\tSelf::{} => {:?},",
+          value.value.to_pascal_case(),
+          value.value
+        ))
+      })
+      .line("};")
+      .line_skip(1)
+      .line("std::io::Write::write_all(out, value.as_bytes())?;")
+      .line_skip(1)
+      .line("Ok(diesel::serialize::IsNull::No)");
+
+    let from_sql_match = r#enum.values.iter().fold(
+      Block::new(level + 3, Some("match std::str::from_utf8(bytes.as_bytes())?")),
+      |acc, value| {
+        acc.line(format!(
+          "{:?} => Ok(Self::{}),",
+          value.value,
+          value.value.to_pascal_case()
+        ))
+      },
+    );
+    let from_sql_match = from_sql_match.line("value => Err(format!(\"unrecognized enum variant '{}'\", value).into()),");
+    let from_sql_block = Block::new(
+      level + 2,
+      Some(format!(
+        "fn from_sql(bytes: {}) -> diesel::deserialize::Result<Self>",
+        value_type
+      )),
+    )
+    .block(from_sql_match);
+
+    acc
+      .line_skip(1)
+      .line("#[derive(Clone, Copy, Debug, PartialEq, Eq, diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)]")
+      .line(format!("#[diesel(sql_type = {})]", sql_type))
+      .block(enum_block)
+      .line_skip(1)
+      .block(
+        Block::new(
+          level + 1,
+          Some(format!(
+            "impl diesel::serialize::ToSql<{}, {}> for {}",
+            sql_type, backend, name
+          )),
+        )
+        .block(to_sql_block),
+      )
+      .line_skip(1)
+      .block(
+        Block::new(
+          level + 1,
+          Some(format!(
+            "impl diesel::deserialize::FromSql<{}, {}> for {}",
+            sql_type, backend, name
+          )),
+        )
+        .block(from_sql_block),
+      )
+  });
+
+  Ok(mod_block)
+}
+
+/// Gets the Rust type of the Postgres types that Diesel deserializes into types of its own.
+fn to_pg_model_type(type_name: &ast::table::ColumnTypeName) -> Option<&'static str> {
+  match type_name {
+    ast::table::ColumnTypeName::Money => Some("diesel::data_types::PgMoney"),
+    ast::table::ColumnTypeName::Inet | ast::table::ColumnTypeName::Cidr => Some("ipnetwork::IpNetwork"),
+    ast::table::ColumnTypeName::MacAddr => Some("[u8; 6]"),
+    ast::table::ColumnTypeName::MacAddr8 => Some("[u8; 8]"),
+    ast::table::ColumnTypeName::Raw(name) if name == "interval" => Some("diesel::data_types::PgInterval"),
+    _ => None,
+  }
+}
+
+/// Gets the Rust type of a model field, following the types that Diesel deserializes.
+fn to_model_type(rust_type: &str) -> String {
+  if let Some(inner) = rust_type.strip_prefix("Vec<").and_then(|rest| rest.strip_suffix('>')) {
+    return format!("Vec<{}>", to_model_type(inner));
+  }
+
+  match rust_type {
+    "DateTimeWithTimeZone" => "chrono::DateTime<chrono::Utc>".to_string(),
+    "Decimal" => "bigdecimal::BigDecimal".to_string(),
+    _ => qualify_rust_type(rust_type),
+  }
+}
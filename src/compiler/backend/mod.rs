@@ -0,0 +1,320 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::generator::Codegen;
+use crate::{NAME, VERSION};
+
+use super::config::*;
+use super::diff::SchemaDiff;
+use super::err::*;
+use super::traits::{annotated_rust_type, ToSqlType};
+use super::warn::*;
+use super::{ref_location, resolve_ref_ident, Output};
+
+use dbml_rs::*;
+
+mod diesel;
+mod sea_orm;
+mod sea_orm_migration;
+mod sql;
+mod sqlx;
+
+pub use self::diesel::Diesel;
+pub use self::sea_orm::SeaOrm;
+pub use self::sea_orm_migration::SeaOrmMigration;
+pub use self::sql::Sql;
+pub use self::sqlx::Sqlx;
+
+/// Code generator of a schema.
+///
+/// The schema given to a backend is already normalized: the many-to-one refs are written from the referencing
+/// side (`>`), the tables are filtered by the configuration, and `config.target` is resolved.
+/// The warnings returned along with the files are appended to the ones raised by the compiler.
+pub trait Backend: fmt::Debug + Send + Sync {
+  /// Name of the backend, as used by the configuration file and the command line.
+  fn name(&self) -> &str;
+
+  /// Generates the files of the schema.
+  fn generate(
+    &self,
+    ast: &analyzer::SemanticSchemaBlock,
+    config: &Config,
+  ) -> Result<Output, CompilerError>;
+
+  /// Generates the incremental migration applying the changes from the old schema to the new one.
+  /// Both schemas are normalized in the same way as the one given to `generate`.
+  fn generate_diff(
+    &self,
+    diff: &SchemaDiff,
+    old: &analyzer::SemanticSchemaBlock,
+    new: &analyzer::SemanticSchemaBlock,
+    config: &Config,
+  ) -> Result<Output, CompilerError> {
+    let _ = (diff, old, new, config);
+
+    Err(CompilerError::Config {
+      message: format!("'{}' backend does not generate migrations", self.name()),
+    })
+  }
+}
+
+/// Gets the built-in backend with the given name.
+pub fn from_name(name: &str) -> Option<Arc<dyn Backend>> {
+  match name {
+    "sea-orm" | "sea_orm" => Some(Arc::new(SeaOrm)),
+    "sea-orm-migration" | "sea_orm_migration" => Some(Arc::new(SeaOrmMigration)),
+    "diesel" => Some(Arc::new(Diesel)),
+    "sqlx" => Some(Arc::new(Sqlx)),
+    "sql" => Some(Arc::new(Sql)),
+    _ => None,
+  }
+}
+
+/// Generates the header of a generated file, followed by the note of the `Project` block (if any).
+pub fn gen_header(ast: &analyzer::SemanticSchemaBlock) -> Codegen {
+  gen_project_doc(ast, Codegen::new().line(format!("//! Generated by {NAME} {VERSION}")))
+}
+
+/// Emits the note of the `Project` block as module-level documentation.
+pub fn gen_project_doc(ast: &analyzer::SemanticSchemaBlock, codegen: Codegen) -> Codegen {
+  gen_project_note(ast, codegen, "//!")
+}
+
+/// Emits the note of the `Project` block as comment lines starting with the given prefix.
+pub fn gen_project_note(ast: &analyzer::SemanticSchemaBlock, codegen: Codegen, prefix: &str) -> Codegen {
+  let note = match ast.project.as_ref().and_then(|project| project.note.as_ref()) {
+    Some(note) => note,
+    None => return codegen,
+  };
+
+  // the parser trims the start of the note, so the indentation is taken from the following lines
+  let lines: Vec<_> = note.trim().lines().map(str::trim_end).collect();
+  let indent = lines
+    .iter()
+    .skip(1)
+    .filter(|line| !line.is_empty())
+    .map(|line| line.len() - line.trim_start().len())
+    .min()
+    .unwrap_or_default();
+
+  lines
+    .iter()
+    .enumerate()
+    .map(|(i, line)| match i {
+      0 => line,
+      _ => line.get(indent..).unwrap_or_default(),
+    })
+    .fold(codegen.line(prefix), |acc, line| match line {
+      "" => acc.line(prefix),
+      line => acc.line(format!("{} {}", prefix, line)),
+    })
+}
+
+/// Collects the expression indexes, for the backends that cannot represent them.
+pub fn expr_index_warnings(ast: &analyzer::SemanticSchemaBlock) -> Vec<CompilerWarning> {
+  let mut warnings = vec![];
+
+  for table in ast.tables.iter() {
+    let name = &table.ident.name;
+    let defs = table.indexes.iter().flat_map(|indexes| indexes.defs.iter());
+
+    for col in defs.flat_map(|def| def.cols.iter()) {
+      if let ast::indexes::IndexesColumnType::Expr(expr) = col {
+        warnings.push(CompilerWarning::new(
+          WarningCode::ExprIndex,
+          format!("expression index `{}` of table '{}' is ignored", expr, name),
+          Location::table(table),
+        ))
+      }
+    }
+  }
+
+  warnings
+}
+
+/// Rejects the multi-dimensional array columns of the Rust backends, unless their type is annotated with `@rust_type`.
+/// The drivers only decode one-dimensional arrays into a `Vec`, and reject the others when the rows are read.
+pub fn check_array_dimensions(ast: &analyzer::SemanticSchemaBlock, config: &Config) -> Result<(), CompilerError> {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+
+  // the arrays of the targets without array types are rejected with the columns
+  if !target.has_array() {
+    return Ok(());
+  }
+
+  for table in ast.tables.iter() {
+    for col in table.cols.iter() {
+      if col.r#type.arrays.len() > 1 && annotated_rust_type(col).is_none() {
+        let location = Location::column(&table.ident, col);
+
+        return Err(CompilerError::UnsupportedType {
+          type_name: col.r#type.to_sql_type(config).map_err(|err| err.at(location.clone()))?,
+          location: Box::new(location),
+        });
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Checks whether the enums are stored as the native enum types of the database.
+pub fn is_native_enum(config: &Config) -> bool {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+
+  matches!(config.enum_type, EnumType::String(_)) && config.is_native_enum && target.has_native_enum()
+}
+
+/// Checks whether the enums are created as types of their own, as in Postgres.
+pub fn has_enum_types(config: &Config) -> bool {
+  is_native_enum(config) && config.target == Some(Target::Postgres)
+}
+
+/// Gets the refs backed by a foreign key on the table, resolved and checked against the schema.
+pub fn table_refs(
+  ast: &analyzer::SemanticSchemaBlock,
+  ident: &ast::table::TableIdent,
+) -> Result<Vec<ast::refs::RefBlock>, CompilerError> {
+  let mut refs = vec![];
+
+  for ref_block in ast.refs.iter() {
+    if !matches!(
+      ref_block.rel,
+      ast::refs::Relation::Many2One | ast::refs::Relation::One2One
+    ) {
+      continue;
+    }
+
+    let location = ref_location(ast, &ref_block.lhs);
+    let lhs = resolve_ref_ident(ast, &ref_block.lhs, &location)?;
+
+    if lhs.table != ident.name || lhs.schema != ident.schema {
+      continue;
+    }
+
+    let rhs = resolve_ref_ident(ast, &ref_block.rhs, &location)?;
+
+    refs.push(ast::refs::RefBlock {
+      lhs,
+      rhs,
+      rel: ref_block.rel.clone(),
+      settings: ref_block.settings.clone(),
+    })
+  }
+
+  Ok(refs)
+}
+
+/// Gets the name of the foreign key of a resolved ref.
+pub fn fk_name(ref_block: &ast::refs::RefBlock) -> String {
+  format!("fk_{}_{}", ref_block.lhs.table, ref_block.lhs.compositions.join("_"))
+}
+
+/// Gets the name of an index, defaulting to `idx_{table}_{cols}`.
+pub fn index_name(table: &str, def: &ast::indexes::IndexesDef) -> String {
+  if let Some(name) = def.settings.as_ref().and_then(|settings| settings.name.clone()) {
+    return name;
+  }
+
+  let parts: Vec<_> = def
+    .cols
+    .iter()
+    .map(|col| match col {
+      ast::indexes::IndexesColumnType::String(name) => name.clone(),
+      // the expressions are turned into identifier-like fragments
+      ast::indexes::IndexesColumnType::Expr(expr) => expr
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+        .to_lowercase(),
+    })
+    .collect();
+
+  format!("idx_{}_{}", table, parts.join("_"))
+}
+
+/// Checks whether the default expression stands for the current date and time.
+pub fn is_current_timestamp(expr: &str) -> bool {
+  matches!(
+    expr.trim().to_lowercase().as_str(),
+    "now()" | "current_timestamp" | "current_timestamp()" | "localtimestamp" | "localtimestamp()"
+  )
+}
+
+/// Checks whether the default expression is an identity (`GENERATED ... AS IDENTITY`) rather than a value.
+pub fn is_identity(value: &ast::table::Value) -> bool {
+  match value {
+    ast::table::Value::Expr(expr) => {
+      let expr = expr.trim().to_lowercase();
+
+      expr.starts_with("generated") && expr.ends_with("identity")
+    }
+    _ => false,
+  }
+}
+
+/// Checks whether the values of a column are generated by the database:
+/// an integer column with the `increment` setting, of a serial type, or with an identity default.
+pub fn is_auto_increment(col: &ast::table::TableColumn) -> bool {
+  use ast::table::ColumnTypeName;
+
+  let is_serial = matches!(
+    col.r#type.type_name,
+    ColumnTypeName::SmallSerial | ColumnTypeName::Serial | ColumnTypeName::BigSerial
+  );
+  let is_integer = match &col.r#type.type_name {
+    ColumnTypeName::SmallInt | ColumnTypeName::Integer | ColumnTypeName::BigInt => true,
+    ColumnTypeName::Raw(name) => matches!(name.as_str(), "tinyint" | "mediumint") || name.ends_with(" unsigned"),
+    _ => false,
+  };
+  let is_generated =
+    col.settings.is_incremental || matches!(&col.settings.default, Some(default) if is_identity(default));
+
+  col.r#type.arrays.is_empty() && (is_serial || is_integer && is_generated)
+}
+
+/// Orders the tables so that every table comes after the tables it references.
+/// The tables of a reference cycle are kept in their declaration order, so some of their refs point forward.
+/// Returns the indexes of the tables in `ast.tables`.
+pub fn order_tables(ast: &analyzer::SemanticSchemaBlock) -> Vec<usize> {
+  let find_table = |ident: &ast::refs::RefIdent| {
+    let ident = ast.indexer.refer_ref_alias(ident);
+
+    ast
+      .tables
+      .iter()
+      .position(|table| table.ident.schema == ident.schema && table.ident.name == ident.table)
+  };
+
+  let mut deps = vec![vec![]; ast.tables.len()];
+
+  for ref_block in ast.refs.iter() {
+    if !matches!(
+      ref_block.rel,
+      ast::refs::Relation::Many2One | ast::refs::Relation::One2One
+    ) {
+      continue;
+    }
+
+    if let (Some(child), Some(parent)) = (find_table(&ref_block.lhs), find_table(&ref_block.rhs)) {
+      if child != parent {
+        deps[child].push(parent);
+      }
+    }
+  }
+
+  let mut out: Vec<usize> = vec![];
+  let mut remaining: Vec<_> = (0..ast.tables.len()).collect();
+
+  while !remaining.is_empty() {
+    let pos = remaining
+      .iter()
+      .position(|i| deps[*i].iter().all(|dep| out.contains(dep)))
+      .unwrap_or(0);
+
+    out.push(remaining.remove(pos));
+  }
+
+  out
+}
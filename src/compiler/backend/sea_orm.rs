@@ -0,0 +1,660 @@
+use std::path::PathBuf;
+
+use crate::compiler::config::*;
+use crate::compiler::err::*;
+use crate::compiler::traits::*;
+use crate::compiler::warn::*;
+use crate::compiler::{ref_location, resolve_ref_ident, Output, OutputFile};
+use crate::generator::{Block, Codegen};
+use crate::DEFAULT_SCHEMA;
+
+use inflector::Inflector;
+
+use dbml_rs::*;
+
+use super::{
+  check_array_dimensions, expr_index_warnings, gen_header, is_auto_increment, is_current_timestamp, is_identity,
+  Backend,
+};
+
+/// Backend generating SeaORM entities.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SeaOrm;
+
+impl Backend for SeaOrm {
+  fn name(&self) -> &str {
+    "sea-orm"
+  }
+
+  fn generate(
+    &self,
+    ast: &analyzer::SemanticSchemaBlock,
+    config: &Config,
+  ) -> Result<Output, CompilerError> {
+    check_array_dimensions(ast, config)?;
+
+    let files = match config.output_mode {
+      OutputMode::File => vec![OutputFile {
+        path: PathBuf::new(),
+        code: gen_file(ast, config)?,
+      }],
+      OutputMode::Directory => gen_dir(ast, config)?,
+    };
+
+    Ok(Output {
+      files,
+      warnings: [expr_index_warnings(ast), default_expr_warnings(ast, config)].concat(),
+    })
+  }
+}
+
+/// Generates every entity as an inline module of a single file.
+fn gen_file(ast: &analyzer::SemanticSchemaBlock, config: &Config) -> Result<String, CompilerError> {
+  let codegen = gen_header(ast);
+
+  let codegen = if ast.enums.is_empty() {
+    codegen
+  } else {
+    codegen
+      .line_skip(1)
+      .line("use sea_orm::entity::prelude::*;")
+  };
+
+  let codegen = ast.tables.iter().try_fold(codegen, |acc, table| {
+    Ok::<_, CompilerError>(acc.line_skip(1).block(gen_entity(ast, table, config, 1)?))
+  })?;
+  let codegen = gen_enum_modules(ast, codegen, config);
+
+  Ok(codegen.to_string())
+}
+
+/// Generates one file per entity along with `mod.rs`, `prelude.rs` and `sea_orm_active_enums.rs`.
+fn gen_dir(
+  ast: &analyzer::SemanticSchemaBlock,
+  config: &Config,
+) -> Result<Vec<OutputFile>, CompilerError> {
+  let header = gen_header(ast);
+
+  let mod_names: Vec<_> = ast
+    .tables
+    .iter()
+    .map(|table| config.naming.module_name(&table.ident.name))
+    .collect();
+
+  let mod_codegen = header
+    .clone()
+    .line_skip(1)
+    .line("pub mod prelude;")
+    .line_skip(1);
+  let mod_codegen = mod_names
+    .iter()
+    .fold(mod_codegen, |acc, name| acc.line(format!("pub mod {};", name)))
+    .line_cond(!ast.enums.is_empty(), "pub mod sea_orm_active_enums;");
+
+  let prelude_codegen = header.clone().line_skip(1);
+  let prelude_codegen = ast.tables.iter().zip(mod_names.iter()).fold(prelude_codegen, |acc, (table, name)| {
+    acc.line(format!(
+      "pub use super::{}::Entity as {};",
+      name,
+      config.naming.type_name(&table.ident.name)
+    ))
+  });
+
+  let mut files = vec![
+    OutputFile {
+      path: PathBuf::from("mod.rs"),
+      code: mod_codegen.to_string(),
+    },
+    OutputFile {
+      path: PathBuf::from("prelude.rs"),
+      code: prelude_codegen.to_string(),
+    },
+  ];
+
+  for (table, name) in ast.tables.iter().zip(mod_names.iter()) {
+    files.push(OutputFile {
+      path: PathBuf::from(format!("{}.rs", name)),
+      code: format!("{}\n{}", header, gen_entity(ast, table, config, 0)?),
+    })
+  }
+
+  if !ast.enums.is_empty() {
+    let codegen = header.line_skip(1).line("use sea_orm::entity::prelude::*;");
+
+    files.push(OutputFile {
+      path: PathBuf::from("sea_orm_active_enums.rs"),
+      code: gen_enum_modules(ast, codegen, config).to_string(),
+    })
+  }
+
+  Ok(files)
+}
+
+/// Generates the entity of a table.
+/// The entity is wrapped in a `pub mod` block unless `level` is zero (i.e. a file of its own).
+fn gen_entity(
+  ast: &analyzer::SemanticSchemaBlock,
+  table: &ast::table::TableBlock,
+  config: &Config,
+  level: usize,
+) -> Result<Block, CompilerError> {
+  let ast::table::TableBlock {
+    ident,
+    cols: fields,
+    ..
+  } = table.clone();
+
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+
+  let table_block = Block::new(level + 1, Some("pub struct Model"));
+  let rel_block = Block::new(level + 1, Some("pub enum Relation"));
+  let mut rel_entity_blocks: Vec<_> = vec![];
+
+  let (rto_vec, rby_vec, rself_vec) = ast.get_table_refs(&ident);
+
+  // newtypes of the primary key and of the foreign keys to the primary keys of the referenced tables
+  let table_newtype = pk_newtype(table, config);
+  let newtypes: Vec<_> = table_newtype
+    .iter()
+    .map(|(col, name, inner)| (col.clone(), name.clone(), inner.clone()))
+    .chain(rto_vec.iter().chain(rself_vec.iter()).filter_map(|ref_block| {
+      if !matches!(ref_block.rel, ast::refs::Relation::Many2One | ast::refs::Relation::One2One) {
+        return None;
+      }
+
+      let lhs = ast.indexer.refer_ref_alias(&ref_block.lhs);
+      let rhs = ast.indexer.refer_ref_alias(&ref_block.rhs);
+      let ref_table = ast
+        .tables
+        .iter()
+        .find(|table| table.ident.schema == rhs.schema && table.ident.name == rhs.table)?;
+
+      match (lhs.compositions.as_slice(), rhs.compositions.as_slice(), pk_newtype(ref_table, config)) {
+        ([from], [to], Some((pk, name, inner))) if *to == pk => Some((
+          from.clone(),
+          match ref_table.ident == ident {
+            true => name,
+            false => format!("super::{}::{}", config.naming.module_name(&rhs.table), name),
+          },
+          inner,
+        )),
+        _ => None,
+      }
+    }))
+    .collect();
+
+  // field listing
+  let table_block = fields.iter().cloned().try_fold(table_block, |acc, field| -> Result<_, CompilerError> {
+    let mut out_fields = vec![];
+
+    let col_type = field
+      .r#type
+      .to_col_type(config)
+      .map_err(|err| err.at(Location::column(&ident, &field)))?;
+
+    if let Some(exp_type) = col_type {
+      out_fields.push(format!(r#"column_type = "{}""#, exp_type.replace('"', r#"\""#)))
+    }
+    if table.meta_indexer.pk_list.contains(&field.name) {
+      out_fields.push("primary_key".to_string());
+
+      // only the single-column keys are generated by the database
+      if table.meta_indexer.pk_list.len() > 1 || !is_auto_increment(&field) {
+        out_fields.push("auto_increment = false".to_string())
+      }
+    }
+    if field.settings.is_nullable {
+      out_fields.push("nullable".to_string())
+    }
+    if table.meta_indexer.indexed_list.contains(&field.name) {
+      out_fields.push("indexed".to_string())
+    }
+    if field.settings.is_unique || table.meta_indexer.unique_list.contains(&field.name) {
+      out_fields.push("unique".to_string())
+    }
+    match entity_default(&field, &target) {
+      // the expression is a Rust expression of the column default, which SeaORM 0.11 cannot declare
+      Some(ast::table::Value::Expr(_)) if config.sea_orm_version == SeaOrmVersion::V0_11 => (),
+      Some(ast::table::Value::Expr(expr)) => {
+        out_fields.push(format!("default_expr = {:?}", format!("Expr::cust({:?})", expr)))
+      }
+      Some(ast::table::Value::String(val)) => out_fields.push(format!(r#"default_value = "{}""#, val)),
+      Some(default) => out_fields.push(format!("default_value = {}", default.to_string())),
+      None => (),
+    }
+    if let Some(type_override) = find_type_override(&field.r#type, config) {
+      out_fields.extend(type_override.attributes.iter().cloned())
+    }
+
+    let field_rust_type = field
+      .r#type
+      .to_rust_type(config)
+      .map_err(|err| err.at(Location::column(&ident, &field)))?;
+    // a newtype only stands for the columns of the same type as the key
+    let field_rust_type = match annotated_rust_type(&field) {
+      Some(rust_type) => rust_type,
+      None => newtypes
+        .iter()
+        .find(|(col, _, inner)| *col == field.name && *inner == field_rust_type)
+        .map_or(field_rust_type, |(_, name, _)| name.clone()),
+    };
+    let field_string = match field.settings.is_nullable {
+      true => format!("Option<{}>", field_rust_type),
+      false => field_rust_type,
+    };
+
+    Ok(
+      acc
+        .line_cond(
+          !out_fields.is_empty(),
+          format!("#[sea_orm({})]", out_fields.join(", ")),
+        )
+        .line(format!("pub {}: {},", field.name, field_string)),
+    )
+  })?;
+
+  // relation listing
+  let has_relations = !(rto_vec.is_empty() && rby_vec.is_empty() && rself_vec.is_empty());
+
+  let rel_block = rself_vec.into_iter().try_fold(rel_block, |acc, rto| -> Result<_, CompilerError> {
+    let location = ref_location(ast, &rto.lhs);
+    let lhs = resolve_ref_ident(ast, &rto.lhs, &location)?;
+    let rhs = resolve_ref_ident(ast, &rto.rhs, &location)?;
+
+    let from_field_pascal = lhs.compositions[0].to_pascal_case();
+    let to_field_pascal = rhs.compositions[0].to_pascal_case();
+
+    let derive = {
+      let mut attrs = vec![
+        format!(r#"belongs_to = "Entity""#),
+        format!(r#"from = "Column::{}""#, from_field_pascal),
+        format!(r#"to = "Column::{}""#, to_field_pascal),
+      ];
+
+      if let Some(settings) = rto.settings {
+        if let Some(action) = settings.on_delete {
+          attrs.push(format!(
+            r#"on_delete = "{}""#,
+            action.to_string().to_pascal_case()
+          ))
+        }
+        if let Some(action) = settings.on_update {
+          attrs.push(format!(
+            r#"on_update = "{}""#,
+            action.to_string().to_pascal_case()
+          ))
+        }
+      }
+
+      format!(r#"#[sea_orm({})]"#, attrs.join(", "))
+    };
+
+    rel_entity_blocks.push(Block::new(level + 1, Some("pub struct SelfReferencingLink")));
+
+    rel_entity_blocks.push(
+      Block::new(level + 1, Some("impl Linked for SelfReferencingLink"))
+        .line("type FromEntity = Entity;")
+        .line("type ToEntity = Entity;")
+        .line_skip(1)
+        .block(
+          Block::new(level + 2, Some("fn link(&self) -> Vec<RelationDef>"))
+            .line("vec![Relation::SelfReferencing.def()]"),
+        ),
+    );
+
+    Ok(acc.line(derive).line("SelfReferencing,"))
+  })?;
+
+  let rel_block = rto_vec.into_iter().try_fold(rel_block, |acc, rto| -> Result<_, CompilerError> {
+    let location = ref_location(ast, &rto.lhs);
+    let lhs = resolve_ref_ident(ast, &rto.lhs, &location)?;
+    let rhs = resolve_ref_ident(ast, &rto.rhs, &location)?;
+
+    let from_field_pascal = lhs.compositions[0].to_pascal_case();
+    let to_field_pascal = rhs.compositions[0].to_pascal_case();
+    let name_pascal = config.naming.type_name(&rhs.table);
+    let name_snake = config.naming.module_name(&rhs.table);
+
+    let derive = match rto.rel {
+      ast::refs::Relation::One2One | ast::refs::Relation::Many2One => {
+        let mut attrs = vec![
+          format!(r#"belongs_to = "super::{}::Entity""#, name_snake),
+          format!(r#"from = "Column::{}""#, from_field_pascal),
+          format!(
+            r#"to = "super::{}::Column::{}""#,
+            name_snake, to_field_pascal
+          ),
+        ];
+
+        if let Some(settings) = rto.settings {
+          if let Some(action) = settings.on_delete {
+            attrs.push(format!(
+              r#"on_delete = "{}""#,
+              action.to_string().to_pascal_case()
+            ))
+          }
+          if let Some(action) = settings.on_update {
+            attrs.push(format!(
+              r#"on_update = "{}""#,
+              action.to_string().to_pascal_case()
+            ))
+          }
+        }
+
+        format!(r#"#[sea_orm({})]"#, attrs.join(", "))
+      }
+      rel => {
+        return Err(CompilerError::UnsupportedRelation {
+          relation: format!("{:?}", rel),
+          location: Box::new(location),
+        })
+      }
+    };
+
+    rel_entity_blocks.push(
+      Block::new(
+        level + 1,
+        Some(format!(
+          "impl Related<super::{}::Entity> for Entity",
+          name_snake
+        )),
+      )
+      .block(
+        Block::new(level + 2, Some("fn to() -> RelationDef"))
+          .line(format!("Relation::{}.def()", name_pascal)),
+      ),
+    );
+
+    Ok(acc.line(derive).line(format!("{},", name_pascal)))
+  })?;
+
+  let rel_block = rby_vec.into_iter().try_fold(rel_block, |acc, rby| -> Result<_, CompilerError> {
+    let location = ref_location(ast, &rby.lhs);
+    let lhs = resolve_ref_ident(ast, &rby.lhs, &location)?;
+    resolve_ref_ident(ast, &rby.rhs, &location)?;
+
+    let name_pascal = config.naming.type_name(&lhs.table);
+    let name_snake = config.naming.module_name(&lhs.table);
+
+    let derive = match rby.rel {
+      ast::refs::Relation::One2One => {
+        format!(r#"#[sea_orm(has_one = "super::{}::Entity")]"#, name_snake)
+      }
+      ast::refs::Relation::Many2One => {
+        format!(r#"#[sea_orm(has_many = "super::{}::Entity")]"#, name_snake)
+      }
+      rel => {
+        return Err(CompilerError::UnsupportedRelation {
+          relation: format!("{:?}", rel),
+          location: Box::new(location),
+        })
+      }
+    };
+
+    rel_entity_blocks.push(
+      Block::new(
+        level + 1,
+        Some(format!(
+          "impl Related<super::{}::Entity> for Entity",
+          name_snake
+        )),
+      )
+      .block(
+        Block::new(level + 2, Some("fn to() -> RelationDef"))
+          .line(format!("Relation::{}.def()", name_pascal)),
+      ),
+    );
+
+    Ok(acc.line(derive).line(format!("{},", name_pascal)))
+  })?;
+
+  // active model listing
+  /* let active_model_item: Vec<_> = fields
+  .iter()
+  .cloned()
+  .filter_map(|field| {
+    let timestamp = "Utc::now().naive_utc()";
+    if field.settings.note == Some("@updated_at".to_string()) {
+      Some(format!("{}: Set({}),", field.name, timestamp))
+    }
+    else if field.settings.default == Some(ast::table::Value::Expr("now()".to_string())) {
+      Some(format!("{}: Set(self.{}.take().or_else(|| Some({}))),", field.name, field.name, timestamp))
+    }
+    else {
+      None
+    }
+  })
+  .chain([
+    "..self".to_string()
+  ])
+  .collect(); */
+
+  // construct mod block
+  let mod_block = match level {
+    0 => Block::new(0, None::<String>),
+    _ => Block::new(level, Some(format!("pub mod {}", config.naming.module_name(&ident.name)))),
+  };
+
+  let mod_block = mod_block
+    .line("use sea_orm::entity::prelude::*;")
+    .line_skip(1)
+    .line("#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]")
+    .line(match target.has_schema() {
+      true => format!(
+        r#"#[sea_orm(table_name = "{}", schema_name = "{}")]"#,
+        &ident.name,
+        &ident.schema.unwrap_or_else(|| DEFAULT_SCHEMA.into())
+      ),
+      false => format!(r#"#[sea_orm(table_name = "{}")]"#, &ident.name),
+    })
+    .block(table_block)
+    .line_skip(1);
+
+  // SeaORM 0.11 entities implement `RelationTrait` by hand when there is no relation, as generated by its CLI
+  let mod_block = match (has_relations, config.sea_orm_version) {
+    (false, SeaOrmVersion::V0_11) => mod_block
+      .line("#[derive(Copy, Clone, Debug, EnumIter)]")
+      .block(rel_block)
+      .line_skip(1)
+      .block(
+        Block::new(level + 1, Some("impl RelationTrait for Relation")).block(
+          Block::new(level + 2, Some("fn def(&self) -> RelationDef")).line(r#"panic!("No RelationDef")"#),
+        ),
+      ),
+    _ => mod_block
+      .line("#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]")
+      .block(rel_block),
+  };
+
+  let mod_block = mod_block
+    .block_vec(rel_entity_blocks)
+    .line_skip(1)
+    .line("impl ActiveModelBehavior for ActiveModel {}");
+
+  let mod_block = match table_newtype {
+    Some((_, name, inner)) => gen_pk_newtype(mod_block.line_skip(1), &name, &inner, level),
+    None => mod_block,
+  };
+
+  Ok(mod_block)
+}
+
+/// Gets the default value of a column as declared by the entity.
+/// The identities are spelled as auto-increments, and MySQL and SQLite spell the current time as `CURRENT_TIMESTAMP`,
+/// which importers also write as a string.
+fn entity_default(col: &ast::table::TableColumn, target: &Target) -> Option<ast::table::Value> {
+  let default = col.settings.default.as_ref().filter(|default| !is_identity(default))?;
+
+  match (target, default) {
+    (
+      Target::MySql | Target::Sqlite,
+      ast::table::Value::Expr(expr) | ast::table::Value::String(expr),
+    ) if is_current_timestamp(expr) => Some(ast::table::Value::Expr("CURRENT_TIMESTAMP".to_string())),
+    _ => Some(default.clone()),
+  }
+}
+
+/// Collects the default expressions left out of the entities, as SeaORM 0.11 cannot declare them.
+fn default_expr_warnings(ast: &analyzer::SemanticSchemaBlock, config: &Config) -> Vec<CompilerWarning> {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+
+  if config.sea_orm_version != SeaOrmVersion::V0_11 {
+    return vec![];
+  }
+
+  let mut warnings = vec![];
+
+  for table in ast.tables.iter() {
+    for col in table.cols.iter() {
+      if let Some(ast::table::Value::Expr(expr)) = entity_default(col, &target) {
+        warnings.push(CompilerWarning::new(
+          WarningCode::DefaultExpr,
+          format!(
+            "default expression `{}` of column '{}' of table '{}' is left out, as SeaORM 0.11 cannot declare it",
+            expr, col.name, table.ident.name
+          ),
+          Location::column(&table.ident, col),
+        ))
+      }
+    }
+  }
+
+  warnings
+}
+
+/// Gets the column, the name and the inner type of the primary key newtype of a table, if enabled.
+/// Only the single-column keys of integer, string or UUID types are wrapped.
+fn pk_newtype(table: &ast::table::TableBlock, config: &Config) -> Option<(String, String, String)> {
+  let pk = match table.meta_indexer.pk_list.as_slice() {
+    [pk] if config.is_pk_newtype => pk,
+    _ => return None,
+  };
+  let col = table.cols.iter().find(|col| &col.name == pk)?;
+
+  if annotated_rust_type(col).is_some() {
+    return None;
+  }
+
+  match col.r#type.to_rust_type(config).ok()?.as_str() {
+    inner @ ("i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "String" | "Uuid") => Some((
+      pk.clone(),
+      format!("{}{}", config.naming.type_name(&table.ident.name), pk.to_pascal_case()),
+      inner.to_string(),
+    )),
+    _ => None,
+  }
+}
+
+/// Generates the newtype of a primary key, along with the traits SeaORM requires of the key and field types.
+fn gen_pk_newtype(block: Block, name: &str, inner: &str, level: usize) -> Block {
+  let derive = match inner {
+    "String" => "#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]",
+    _ => "#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]",
+  };
+  let impl_block = |header: String| Block::new(level + 1, Some(header));
+  let fn_block = |header: &str| Block::new(level + 2, Some(header));
+
+  block
+    .line(derive)
+    .line(format!("pub struct {}(pub {});", name, inner))
+    .line_skip(1)
+    .block(
+      impl_block(format!("impl From<{}> for Value", name))
+        .block(fn_block(&format!("fn from(value: {}) -> Self", name)).line("value.0.into()")),
+    )
+    .line_skip(1)
+    .block(
+      impl_block(format!("impl sea_orm::TryGetable for {}", name)).block(
+        fn_block("fn try_get_by<I: sea_orm::ColIdx>(res: &QueryResult, index: I) -> Result<Self, sea_orm::TryGetError>")
+          .line(format!("<{} as sea_orm::TryGetable>::try_get_by(res, index).map(Self)", inner)),
+      ),
+    )
+    .line_skip(1)
+    .block(
+      impl_block(format!("impl sea_orm::sea_query::ValueType for {}", name))
+        .block(
+          fn_block("fn try_from(v: Value) -> Result<Self, sea_orm::sea_query::ValueTypeErr>")
+            .line(format!("<{} as sea_orm::sea_query::ValueType>::try_from(v).map(Self)", inner)),
+        )
+        .line_skip(1)
+        .block(fn_block("fn type_name() -> String").line(format!(r#""{}".to_string()"#, name)))
+        .line_skip(1)
+        .block(
+          fn_block("fn array_type() -> sea_orm::sea_query::ArrayType")
+            .line(format!("<{} as sea_orm::sea_query::ValueType>::array_type()", inner)),
+        )
+        .line_skip(1)
+        .block(
+          fn_block("fn column_type() -> ColumnType")
+            .line(format!("<{} as sea_orm::sea_query::ValueType>::column_type()", inner)),
+        ),
+    )
+    .line_skip(1)
+    .block(
+      impl_block(format!("impl sea_orm::sea_query::Nullable for {}", name)).block(
+        fn_block("fn null() -> Value").line(format!("<{} as sea_orm::sea_query::Nullable>::null()", inner)),
+      ),
+    )
+    .line_skip(1)
+    .block(
+      impl_block(format!("impl sea_orm::TryFromU64 for {}", name)).block(
+        fn_block("fn try_from_u64(n: u64) -> Result<Self, DbErr>")
+          .line(format!("<{} as sea_orm::TryFromU64>::try_from_u64(n).map(Self)", inner)),
+      ),
+    )
+}
+
+fn gen_enum_modules(ast: &analyzer::SemanticSchemaBlock, codegen: Codegen, config: &Config) -> Codegen {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+
+  let rs_type = match config.enum_type {
+    EnumType::Integer => "i32".to_string(),
+    EnumType::String(_) => "String".to_string()
+  };
+
+  let db_type = match config.enum_type {
+    EnumType::Integer => "Integer".to_string(),
+    EnumType::String(_) if config.is_native_enum && target.has_native_enum() => "Enum".to_string(),
+    EnumType::String(size) => string_col_type(size.map(|size| size.to_string()), config),
+  };
+  
+  ast.enums.clone().into_iter().fold(codegen, |acc, r#enum| {
+    let ast::enums::EnumBlock {
+      ident: ast::enums::EnumIdent { name, schema },
+      values,
+    } = r#enum;
+
+    let enum_block = Block::new(1, Some(format!("pub enum {}", name.to_pascal_case())));
+
+    let enum_block = values.into_iter().enumerate().fold(enum_block, |acc, (i, value)| {
+      let value_name = value.value;
+      let value = match config.enum_type {
+        EnumType::Integer => format!(r#"num_value = {}"#, i),
+        EnumType::String(_) => format!(r#"string_value = "{}""#, value_name)
+      };
+
+      acc
+        .line(format!(r#"#[sea_orm({})]"#, value))
+        .line(format!("{},", value_name.to_pascal_case()))
+    });
+
+    acc
+      .line_skip(1)
+      .line("#[derive(Clone, Debug, PartialEq, EnumIter, DeriveActiveEnum)]")
+      // `schema_name` is ignored by SeaORM 0.11, and rejected by the later releases
+      .line(match target.has_schema() && config.sea_orm_version == SeaOrmVersion::V0_11 {
+        true => format!(
+          r#"#[sea_orm(rs_type = "{}", db_type = "{}", enum_name = "{}", schema_name = "{}")]"#,
+          rs_type,
+          db_type,
+          name,
+          schema.unwrap_or_else(|| DEFAULT_SCHEMA.into())
+        ),
+        false => format!(
+          r#"#[sea_orm(rs_type = "{}", db_type = "{}", enum_name = "{}")]"#,
+          rs_type, db_type, name
+        ),
+      })
+      .block(enum_block)
+  })
+}
@@ -0,0 +1,869 @@
+use std::path::PathBuf;
+
+use crate::compiler::config::*;
+use crate::compiler::diff::{self, *};
+use crate::compiler::err::*;
+use crate::compiler::traits::*;
+use crate::compiler::warn::*;
+use crate::compiler::{ref_location, Output, OutputFile};
+use crate::generator::{Block, Codegen};
+use crate::DEFAULT_SCHEMA;
+
+use inflector::Inflector;
+
+use dbml_rs::*;
+
+use super::sql::gen_alter_keys;
+use super::{
+  expr_index_warnings, fk_name, gen_header, has_enum_types, index_name, is_auto_increment, is_current_timestamp,
+  is_identity, is_native_enum, order_tables, table_refs, Backend,
+};
+
+/// Name of the migration module in the directory output.
+const MIGRATION_NAME: &str = "m00000000_000001_create_schema";
+
+/// Backend generating a sea-orm-migration `MigrationTrait` that creates the schema.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SeaOrmMigration;
+
+impl Backend for SeaOrmMigration {
+  fn name(&self) -> &str {
+    "sea-orm-migration"
+  }
+
+  fn generate(
+    &self,
+    ast: &analyzer::SemanticSchemaBlock,
+    config: &Config,
+  ) -> Result<Output, CompilerError> {
+    let files = match config.output_mode {
+      OutputMode::File => vec![OutputFile {
+        path: PathBuf::new(),
+        code: gen_migration(ast, config)?.to_string(),
+      }],
+      OutputMode::Directory => {
+        let migrations_block = Block::new(2, Some("fn migrations() -> Vec<Box<dyn MigrationTrait>>"))
+          .line(format!("vec![Box::new({}::Migration)]", MIGRATION_NAME));
+
+        let mod_codegen = gen_header(ast)
+          .line_skip(1)
+          .line("pub use sea_orm_migration::prelude::*;")
+          .line_skip(1)
+          .line(format!("mod {};", MIGRATION_NAME))
+          .line_skip(1)
+          .line("pub struct Migrator;")
+          .line_skip(1)
+          .line("#[async_trait::async_trait]")
+          .block(Block::new(1, Some("impl MigratorTrait for Migrator")).block(migrations_block));
+
+        vec![
+          OutputFile {
+            path: PathBuf::from("mod.rs"),
+            code: mod_codegen.to_string(),
+          },
+          OutputFile {
+            path: PathBuf::from(format!("{}.rs", MIGRATION_NAME)),
+            code: gen_migration(ast, config)?.to_string(),
+          },
+        ]
+      }
+    };
+
+    Ok(Output {
+      files,
+      warnings: expr_index_warnings(ast),
+    })
+  }
+
+  /// Generates a single migration, whose `down` applies the changes from the new schema back to the old one.
+  fn generate_diff(
+    &self,
+    diff: &SchemaDiff,
+    old: &analyzer::SemanticSchemaBlock,
+    new: &analyzer::SemanticSchemaBlock,
+    config: &Config,
+  ) -> Result<Output, CompilerError> {
+    let reverse_diff = diff::diff(new, old);
+
+    let mut idens = Idens::default();
+
+    let up_stmts = gen_alter_stmts(diff, new, config, &mut idens, false)?;
+    let down_stmts = gen_alter_stmts(&reverse_diff, old, config, &mut idens, true)?;
+
+    let has_types = has_enum_types(config)
+      && diff.changes.iter().any(|change| {
+        matches!(
+          change,
+          Change::CreateEnum(_)
+            | Change::DropEnum(_)
+            | Change::AddEnumValue { .. }
+            | Change::DropEnumValue { .. }
+        )
+      });
+
+    // the expression indexes are left out, as in the full migration
+    let mut warnings = vec![];
+
+    for change in diff.changes.iter() {
+      let (table, defs) = match change {
+        Change::CreateTable(table) => (
+          &table.ident,
+          table.indexes.iter().flat_map(|indexes| indexes.defs.iter()).collect(),
+        ),
+        Change::AddIndex { table, index } => (table, vec![index]),
+        _ => continue,
+      };
+
+      for col in defs.iter().flat_map(|def| def.cols.iter()) {
+        if let ast::indexes::IndexesColumnType::Expr(expr) = col {
+          warnings.push(CompilerWarning::new(
+            WarningCode::ExprIndex,
+            format!("expression index `{}` of table '{}' is ignored", expr, table.name),
+            Location::table_ident(table),
+          ))
+        }
+      }
+    }
+
+    Ok(Output {
+      files: vec![OutputFile {
+        path: PathBuf::new(),
+        code: gen_migration_file(new, up_stmts, down_stmts, &idens, has_types, config).to_string(),
+      }],
+      warnings,
+    })
+  }
+}
+
+/// Identifier enums of the tables and the enum types, along with their variants, in the order of their use.
+#[derive(Debug, Default)]
+struct Idens(Vec<(String, Vec<String>)>);
+
+impl Idens {
+  /// Gets the identifier standing for the name of a table or an enum type.
+  fn table(&mut self, name: &str) -> String {
+    self.variants(name);
+
+    format!("{}::Table", name.to_pascal_case())
+  }
+
+  /// Gets the identifier of a column or an enum value.
+  fn variant(&mut self, name: &str, variant: &str) -> String {
+    let variants = self.variants(name);
+
+    if !variants.iter().any(|item| item == variant) {
+      variants.push(variant.to_string())
+    }
+
+    format!("{}::{}", name.to_pascal_case(), variant.to_pascal_case())
+  }
+
+  fn variants(&mut self, name: &str) -> &mut Vec<String> {
+    let pos = match self.0.iter().position(|(item, _)| item == name) {
+      Some(pos) => pos,
+      None => {
+        self.0.push((name.to_string(), vec![]));
+        self.0.len() - 1
+      }
+    };
+
+    &mut self.0[pos].1
+  }
+}
+
+/// Gets the identifier enum variant of a name, along with the attribute renaming it when the derived name differs.
+fn iden_variant(name: &str, config: &Config) -> (String, Option<String>) {
+  let variant = name.to_pascal_case();
+
+  match (variant.to_snake_case() == name, config.sea_orm_version) {
+    (true, _) => (variant, None),
+    (false, SeaOrmVersion::V0_11) => (variant, Some(format!(r#"#[iden = "{}"]"#, name))),
+    (false, _) => (variant, Some(format!(r#"#[sea_orm(iden = "{}")]"#, name))),
+  }
+}
+
+/// Gets the table (or type) reference, qualified by the schema on Postgres.
+fn gen_type_ref(name: &str, schema: &Option<String>, config: &Config, idens: &mut Idens) -> String {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+
+  match schema {
+    Some(schema) if target.has_schema() && schema != DEFAULT_SCHEMA => {
+      format!(r#"(Alias::new("{}"), {})"#, schema, idens.table(name))
+    }
+    _ => idens.table(name),
+  }
+}
+
+/// Gets the column identifiers of a table, as a tuple when there are more than one.
+fn gen_col_list(table: &str, cols: &[String], idens: &mut Idens) -> String {
+  let cols: Vec<_> = cols.iter().map(|col| idens.variant(table, col)).collect();
+
+  match cols.as_slice() {
+    [col] => col.clone(),
+    _ => format!("({})", cols.join(", ")),
+  }
+}
+
+/// Gets the value identifiers of an enum.
+fn gen_enum_values(r#enum: &ast::enums::EnumBlock, idens: &mut Idens) -> Vec<String> {
+  r#enum
+    .values
+    .iter()
+    .map(|value| idens.variant(&r#enum.ident.name, &value.value))
+    .collect()
+}
+
+/// Generates a statement executed by the schema manager.
+/// The calls are chained onto the builder, and may span multiple lines.
+fn gen_statement(method: &str, builder: &str, calls: &[String]) -> Vec<String> {
+  let mut lines = vec![
+    "manager".to_string(),
+    format!("\t.{}(", method),
+    format!("\t\t{}", builder),
+  ];
+
+  for call in calls.iter() {
+    lines.extend(call.lines().map(|line| format!("\t\t\t{}", line)));
+  }
+
+  lines.extend([
+    "\t\t\t.to_owned(),".to_string(),
+    "\t)".to_string(),
+    "\t.await?;".to_string(),
+  ]);
+
+  lines
+}
+
+/// Generates the column definition of a column.
+/// The definition of a modified column leaves out the keys, which are already set on the table,
+/// and states its nullability either way.
+fn gen_column_def(
+  ast: &analyzer::SemanticSchemaBlock,
+  table: &ast::table::TableBlock,
+  col: &ast::table::TableColumn,
+  config: &Config,
+  idens: &mut Idens,
+  is_modified: bool,
+) -> Result<String, CompilerError> {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+  let ident = &table.ident;
+  let pk_list = &table.meta_indexer.pk_list;
+
+  let col_type = match &col.r#type.type_name {
+    ast::table::ColumnTypeName::Enum(name) if is_native_enum(config) => {
+      let values = ast
+        .enums
+        .iter()
+        .find(|r#enum| &r#enum.ident.name == name)
+        .map(|r#enum| gen_enum_values(r#enum, idens))
+        .unwrap_or_default();
+
+      format!("enumeration({}, [{}])", idens.table(name), values.join(", "))
+    }
+    _ => col
+      .r#type
+      .to_column_def(config)
+      .map_err(|err| err.at(Location::column(ident, col)))?,
+  };
+
+  let mut def = format!("ColumnDef::new({}).{}", idens.variant(&ident.name, &col.name), col_type);
+
+  match col.settings.is_nullable {
+    true if is_modified => def.push_str(".null()"),
+    true => (),
+    false => def.push_str(".not_null()"),
+  }
+  // Postgres spells the auto-increment as a type, which is not modified
+  if is_auto_increment(col) && !(is_modified && target == Target::Postgres) {
+    def.push_str(".auto_increment()")
+  }
+  if pk_list.len() == 1 && pk_list[0] == col.name && !is_modified {
+    def.push_str(".primary_key()")
+  }
+  if col.settings.is_unique && !is_modified {
+    def.push_str(".unique_key()")
+  }
+  // the identities are spelled as auto-increments
+  if let Some(default) = col.settings.default.as_ref().filter(|default| !is_identity(default)) {
+    let default = match default {
+      // MySQL and SQLite spell the current time as `CURRENT_TIMESTAMP`
+      ast::table::Value::Expr(expr) | ast::table::Value::String(expr)
+        if matches!(target, Target::MySql | Target::Sqlite) && is_current_timestamp(expr) =>
+      {
+        Some(r#"Expr::cust("CURRENT_TIMESTAMP")"#.to_string())
+      }
+      ast::table::Value::String(val) | ast::table::Value::HexColor(val) => Some(format!("{:?}", val)),
+      ast::table::Value::Integer(val) => Some(val.to_string()),
+      ast::table::Value::Decimal(val) => Some(format!("{:?}", val)),
+      ast::table::Value::Bool(val) => Some(val.to_string()),
+      ast::table::Value::Expr(val) => Some(format!("Expr::cust({:?})", val)),
+      ast::table::Value::Null => None,
+    };
+
+    if let Some(default) = default {
+      def.push_str(&format!(".default({})", default))
+    }
+  }
+
+  Ok(def)
+}
+
+/// Generates the calls building the foreign key of a resolved ref.
+fn gen_fk_calls(ref_block: &ast::refs::RefBlock, config: &Config, idens: &mut Idens) -> Vec<String> {
+  let ast::refs::RefBlock { lhs, rhs, .. } = ref_block;
+
+  let mut calls = vec![
+    format!(
+      ".from({}, {})",
+      gen_type_ref(&lhs.table, &lhs.schema, config, idens),
+      gen_col_list(&lhs.table, &lhs.compositions, idens)
+    ),
+    format!(
+      ".to({}, {})",
+      gen_type_ref(&rhs.table, &rhs.schema, config, idens),
+      gen_col_list(&rhs.table, &rhs.compositions, idens)
+    ),
+  ];
+
+  if let Some(settings) = &ref_block.settings {
+    if let Some(action) = &settings.on_delete {
+      calls.push(format!(
+        ".on_delete(ForeignKeyAction::{})",
+        action.to_string().to_pascal_case()
+      ))
+    }
+    if let Some(action) = &settings.on_update {
+      calls.push(format!(
+        ".on_update(ForeignKeyAction::{})",
+        action.to_string().to_pascal_case()
+      ))
+    }
+  }
+
+  calls
+}
+
+/// Generates the statement creating the foreign key of a resolved ref on an existing table.
+fn gen_create_fk(ref_block: &ast::refs::RefBlock, config: &Config, idens: &mut Idens) -> Vec<String> {
+  gen_statement(
+    "create_foreign_key",
+    "ForeignKey::create()",
+    &[
+      vec![format!(".name({:?})", fk_name(ref_block))],
+      gen_fk_calls(ref_block, config, idens),
+    ]
+    .concat(),
+  )
+}
+
+/// Generates the statement dropping the foreign key of a resolved ref.
+fn gen_drop_fk(ref_block: &ast::refs::RefBlock, config: &Config, idens: &mut Idens) -> Vec<String> {
+  let lhs = &ref_block.lhs;
+
+  gen_statement(
+    "drop_foreign_key",
+    "ForeignKey::drop()",
+    &[
+      format!(".name({:?})", fk_name(ref_block)),
+      format!(".table({})", gen_type_ref(&lhs.table, &lhs.schema, config, idens)),
+    ],
+  )
+}
+
+/// Generates the statement creating an enum type.
+fn gen_create_type(r#enum: &ast::enums::EnumBlock, config: &Config, idens: &mut Idens) -> Vec<String> {
+  let ast::enums::EnumIdent { name, schema } = &r#enum.ident;
+
+  gen_statement(
+    "create_type",
+    "Type::create()",
+    &[
+      format!(".as_enum({})", gen_type_ref(name, schema, config, idens)),
+      format!(".values([{}])", gen_enum_values(r#enum, idens).join(", ")),
+    ],
+  )
+}
+
+/// Generates the statement dropping an enum type.
+fn gen_drop_type(ident: &ast::enums::EnumIdent, config: &Config, idens: &mut Idens) -> Vec<String> {
+  gen_statement(
+    "drop_type",
+    "Type::drop()",
+    &[format!(".name({})", gen_type_ref(&ident.name, &ident.schema, config, idens))],
+  )
+}
+
+/// Generates the statement creating a table, with the foreign keys of the given refs.
+fn gen_create_table(
+  ast: &analyzer::SemanticSchemaBlock,
+  table: &ast::table::TableBlock,
+  refs: &[ast::refs::RefBlock],
+  config: &Config,
+  idens: &mut Idens,
+) -> Result<Vec<String>, CompilerError> {
+  let ident = &table.ident;
+  let pk_list = &table.meta_indexer.pk_list;
+
+  let mut calls = vec![
+    format!(".table({})", gen_type_ref(&ident.name, &ident.schema, config, idens)),
+    ".if_not_exists()".to_string(),
+  ];
+
+  for col in table.cols.iter() {
+    calls.push(format!(".col({})", gen_column_def(ast, table, col, config, idens, false)?));
+  }
+
+  if pk_list.len() > 1 {
+    let cols: Vec<_> = pk_list
+      .iter()
+      .map(|col| format!(".col({})", idens.variant(&ident.name, col)))
+      .collect();
+
+    calls.push(format!(".primary_key(Index::create(){})", cols.join("")));
+  }
+
+  for ref_block in refs.iter() {
+    let fk_lines: Vec<_> = gen_fk_calls(ref_block, config, idens)
+      .iter()
+      .map(|call| format!("\t\t{}", call))
+      .collect();
+
+    calls.push(format!(
+      ".foreign_key(\n\tForeignKey::create()\n\t\t.name({:?})\n{},\n)",
+      fk_name(ref_block),
+      fk_lines.join("\n")
+    ));
+  }
+
+  Ok(gen_statement("create_table", "Table::create()", &calls))
+}
+
+/// Generates the statement dropping a table.
+fn gen_drop_table(ident: &ast::table::TableIdent, config: &Config, idens: &mut Idens) -> Vec<String> {
+  gen_statement(
+    "drop_table",
+    "Table::drop()",
+    &[format!(".table({})", gen_type_ref(&ident.name, &ident.schema, config, idens))],
+  )
+}
+
+/// Generates the statement creating an index.
+/// The primary keys are declared by the tables instead, and the expression indexes are not supported by the builder.
+fn gen_create_index(
+  table: &ast::table::TableIdent,
+  def: &ast::indexes::IndexesDef,
+  config: &Config,
+  idens: &mut Idens,
+) -> Option<Vec<String>> {
+  let settings = def.settings.clone().unwrap_or_default();
+
+  let cols: Option<Vec<_>> = def
+    .cols
+    .iter()
+    .map(|col| match col {
+      ast::indexes::IndexesColumnType::String(col) => Some(col.clone()),
+      ast::indexes::IndexesColumnType::Expr(_) => None,
+    })
+    .collect();
+  let cols = match cols {
+    Some(cols) if !settings.is_pk => cols,
+    _ => return None,
+  };
+
+  let mut calls = vec![
+    format!(".name({:?})", index_name(&table.name, def)),
+    format!(".table({})", gen_type_ref(&table.name, &table.schema, config, idens)),
+  ];
+
+  calls.extend(
+    cols
+      .iter()
+      .map(|col| format!(".col({})", idens.variant(&table.name, col))),
+  );
+
+  if settings.is_unique {
+    calls.push(".unique()".to_string())
+  }
+  if let Some(index_type) = &settings.r#type {
+    calls.push(match index_type {
+      ast::indexes::IndexesType::BTree => ".index_type(IndexType::BTree)".to_string(),
+      ast::indexes::IndexesType::Hash => ".index_type(IndexType::Hash)".to_string(),
+      ast::indexes::IndexesType::Gin => {
+        r#".index_type(IndexType::Custom(Alias::new("gin").into_iden()))"#.to_string()
+      }
+      ast::indexes::IndexesType::Gist => {
+        r#".index_type(IndexType::Custom(Alias::new("gist").into_iden()))"#.to_string()
+      }
+    })
+  }
+
+  Some(gen_statement("create_index", "Index::create()", &calls))
+}
+
+/// Generates the statement altering a table with the given call.
+fn gen_alter_table(
+  table: &ast::table::TableIdent,
+  call: String,
+  config: &Config,
+  idens: &mut Idens,
+) -> Vec<String> {
+  gen_statement(
+    "alter_table",
+    "Table::alter()",
+    &[
+      format!(".table({})", gen_type_ref(&table.name, &table.schema, config, idens)),
+      call,
+    ],
+  )
+}
+
+/// Generates the statement running the given SQL on the connection of the schema manager.
+/// `ConnectionTrait` is in the prelude of sea-orm-migration.
+fn gen_unprepared(sql: &str) -> Vec<String> {
+  vec![
+    "manager".to_string(),
+    "\t.get_connection()".to_string(),
+    format!("\t.execute_unprepared({:?})", sql),
+    "\t.await?;".to_string(),
+  ]
+}
+
+/// Generates the migration creating the enums, tables, foreign keys and indexes.
+fn gen_migration(ast: &analyzer::SemanticSchemaBlock, config: &Config) -> Result<Codegen, CompilerError> {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+
+  // the identifiers are declared in the order of the schema
+  let mut idens = Idens::default();
+
+  for table in ast.tables.iter() {
+    idens.table(&table.ident.name);
+
+    for col in table.cols.iter() {
+      idens.variant(&table.ident.name, &col.name);
+    }
+  }
+  if is_native_enum(config) {
+    for r#enum in ast.enums.iter() {
+      idens.table(&r#enum.ident.name);
+      gen_enum_values(r#enum, &mut idens);
+    }
+  }
+
+  let order = order_tables(ast);
+  let position = |ident: &ast::refs::RefIdent| {
+    order.iter().position(|i| {
+      let table = &ast.tables[*i].ident;
+
+      table.schema == ident.schema && table.name == ident.table
+    })
+  };
+
+  let mut up_stmts: Vec<Vec<String>> = vec![];
+  let mut drop_type_stmts: Vec<Vec<String>> = vec![];
+  let mut drop_fk_stmts: Vec<Vec<String>> = vec![];
+  let mut index_stmts: Vec<Vec<String>> = vec![];
+  let mut fk_stmts: Vec<Vec<String>> = vec![];
+
+  // enum types
+  if has_enum_types(config) {
+    for r#enum in ast.enums.iter() {
+      up_stmts.push(gen_create_type(r#enum, config, &mut idens));
+      drop_type_stmts.push(gen_drop_type(&r#enum.ident, config, &mut idens));
+    }
+  }
+
+  // tables
+  for (pos, table) in order.iter().map(|i| &ast.tables[*i]).enumerate() {
+    let mut refs = vec![];
+
+    for ref_block in table_refs(ast, &table.ident)? {
+      // the refs to a table that is created later are added afterwards, except on SQLite,
+      // which does not alter constraints and does not check the referenced table on creation
+      let rhs_pos = position(&ref_block.rhs).ok_or_else(|| CompilerError::UnresolvedTable {
+        name: ref_block.rhs.table.clone(),
+        location: Box::new(ref_location(ast, &ref_block.lhs)),
+      })?;
+
+      match rhs_pos > pos && target != Target::Sqlite {
+        true => {
+          fk_stmts.push(gen_create_fk(&ref_block, config, &mut idens));
+          drop_fk_stmts.push(gen_drop_fk(&ref_block, config, &mut idens));
+        }
+        false => refs.push(ref_block),
+      }
+    }
+
+    up_stmts.push(gen_create_table(ast, table, &refs, config, &mut idens)?);
+
+    // indexes
+    let defs = table.indexes.iter().flat_map(|indexes| indexes.defs.iter());
+
+    for def in defs {
+      index_stmts.extend(gen_create_index(&table.ident, def, config, &mut idens));
+    }
+  }
+
+  // the tables are dropped in the reverse order of their creation, before the types they use
+  let drop_tables: Vec<_> = order
+    .iter()
+    .rev()
+    .map(|i| gen_drop_table(&ast.tables[*i].ident, config, &mut idens))
+    .collect();
+  let down_stmts = [drop_fk_stmts, drop_tables, drop_type_stmts].concat();
+  let up_stmts = [up_stmts, fk_stmts, index_stmts].concat();
+
+  Ok(gen_migration_file(
+    ast,
+    up_stmts,
+    down_stmts,
+    &idens,
+    has_enum_types(config) && !ast.enums.is_empty(),
+    config,
+  ))
+}
+
+/// Generates the statements applying the changes of a diff, with the schema they lead to.
+/// When reverting the changes, the enum values that cannot be dropped are left in place.
+fn gen_alter_stmts(
+  diff: &SchemaDiff,
+  ast: &analyzer::SemanticSchemaBlock,
+  config: &Config,
+  idens: &mut Idens,
+  is_reverted: bool,
+) -> Result<Vec<Vec<String>>, CompilerError> {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+
+  let find_table = |ident: &ast::table::TableIdent| {
+    ast
+      .tables
+      .iter()
+      .find(|table| table.ident.name == ident.name && table.ident.schema == ident.schema)
+      .ok_or_else(|| CompilerError::UnresolvedTable {
+        name: ident.name.clone(),
+        location: Box::new(Location::table_ident(ident)),
+      })
+  };
+  let unsupported = |feature: &str, location: Location| CompilerError::UnsupportedFeature {
+    feature: feature.to_string(),
+    target: target.clone(),
+    location: Box::new(location),
+  };
+
+  let mut stmts: Vec<Vec<String>> = vec![];
+
+  for change in diff.changes.iter() {
+    match change {
+      Change::CreateEnum(r#enum) if has_enum_types(config) => {
+        stmts.push(gen_create_type(r#enum, config, idens))
+      }
+      Change::DropEnum(r#enum) if has_enum_types(config) => {
+        stmts.push(gen_drop_type(&r#enum.ident, config, idens))
+      }
+      Change::AddEnumValue { r#enum, value } if has_enum_types(config) => stmts.push(gen_statement(
+        "alter_type",
+        "Type::alter()",
+        &[
+          format!(".name({})", gen_type_ref(&r#enum.name, &r#enum.schema, config, idens)),
+          format!(".add_value({})", idens.variant(&r#enum.name, value)),
+        ],
+      )),
+      Change::DropEnumValue { r#enum, value } if has_enum_types(config) && is_reverted => {
+        stmts.push(vec![format!(
+          "// the value '{}' of enum '{}' is kept, as Postgres does not drop enum values",
+          value, r#enum.name
+        )])
+      }
+      Change::DropEnumValue { r#enum, .. } if has_enum_types(config) => {
+        return Err(unsupported(
+          "dropping an enum value",
+          Location::enum_block(r#enum),
+        ))
+      }
+      // the inline enums of MySQL are changed along with the columns using them
+      Change::AddEnumValue { r#enum, .. } | Change::DropEnumValue { r#enum, .. } if is_native_enum(config) => {
+        for table in ast.tables.iter() {
+          if diff.is_created(&table.ident.name, &table.ident.schema) {
+            continue;
+          }
+
+          for col in table.cols.iter() {
+            if col.r#type.type_name != ast::table::ColumnTypeName::Enum(r#enum.name.clone()) {
+              continue;
+            }
+
+            let def = gen_column_def(ast, table, col, config, idens, true)?;
+            let stmt = gen_alter_table(&table.ident, format!(".modify_column({})", def), config, idens);
+
+            if !stmts.contains(&stmt) {
+              stmts.push(stmt)
+            }
+          }
+        }
+      }
+      Change::CreateEnum(_) | Change::DropEnum(_) | Change::AddEnumValue { .. } | Change::DropEnumValue { .. } => (),
+      Change::DropRef(ref_block) => match target {
+        Target::Sqlite => {
+          return Err(unsupported(
+            "dropping a foreign key",
+            ref_location(ast, &ref_block.lhs),
+          ))
+        }
+        _ => stmts.push(gen_drop_fk(ref_block, config, idens)),
+      },
+      Change::DropIndex { table, index } => stmts.push(gen_statement(
+        "drop_index",
+        "Index::drop()",
+        &[
+          format!(".name({:?})", index_name(&table.name, index)),
+          format!(".table({})", gen_type_ref(&table.name, &table.schema, config, idens)),
+        ],
+      )),
+      Change::DropTable(table) => stmts.push(gen_drop_table(&table.ident, config, idens)),
+      Change::CreateTable(table) => {
+        // SQLite creates every foreign key along with the table, as it does not alter constraints
+        let refs: Vec<_> = table_refs(ast, &table.ident)?
+          .into_iter()
+          .filter(|ref_block| {
+            target == Target::Sqlite || !diff.changes.contains(&Change::AddRef(ref_block.clone()))
+          })
+          .collect();
+
+        stmts.push(gen_create_table(ast, table, &refs, config, idens)?);
+
+        let defs = table.indexes.iter().flat_map(|indexes| indexes.defs.iter());
+
+        for def in defs {
+          stmts.extend(gen_create_index(&table.ident, def, config, idens));
+        }
+      }
+      Change::AddColumn { table, column } => {
+        let def = gen_column_def(ast, find_table(table)?, column, config, idens, false)?;
+
+        stmts.push(gen_alter_table(table, format!(".add_column({})", def), config, idens))
+      }
+      Change::AlterColumn { table, old, new } => {
+        if target == Target::Sqlite {
+          return Err(unsupported(
+            "altering a column",
+            Location::column(table, new),
+          ));
+        }
+
+        // the keys are not altered by the builder, so they are changed by the statements of the SQL backend
+        let (add_keys, drop_keys) = gen_alter_keys(table, old, new, &target);
+
+        stmts.extend(add_keys.iter().map(|sql| gen_unprepared(sql)));
+
+        // Postgres spells the auto-increment as a type, whose sequence is altered along with the keys
+        let is_increment_changed =
+          target != Target::Postgres && old.settings.is_incremental != new.settings.is_incremental;
+
+        if is_definition_changed(old, new) || is_increment_changed {
+          let mut def = gen_column_def(ast, find_table(table)?, new, config, idens, true)?;
+
+          // Postgres keeps the default value unless it is replaced
+          if target == Target::Postgres && old.settings.default.is_some() && new.settings.default.is_none() {
+            def.push_str(r#".default(Expr::cust("NULL"))"#)
+          }
+
+          stmts.push(gen_alter_table(table, format!(".modify_column({})", def), config, idens))
+        }
+
+        stmts.extend(drop_keys.iter().map(|sql| gen_unprepared(sql)));
+      }
+      Change::DropColumn { table, column } => {
+        let col = idens.variant(&table.name, &column.name);
+
+        stmts.push(gen_alter_table(table, format!(".drop_column({})", col), config, idens))
+      }
+      Change::AddRef(ref_block) => {
+        let lhs = &ref_block.lhs;
+
+        match target {
+          Target::Sqlite if diff.is_created(&lhs.table, &lhs.schema) => (),
+          Target::Sqlite => {
+            return Err(unsupported(
+              "adding a foreign key",
+              ref_location(ast, lhs),
+            ))
+          }
+          _ => stmts.push(gen_create_fk(ref_block, config, idens)),
+        }
+      }
+      Change::AddIndex { table, index } => stmts.extend(gen_create_index(table, index, config, idens)),
+    }
+  }
+
+  Ok(stmts)
+}
+
+/// Generates the migration file running the statements, followed by the identifier enums.
+fn gen_migration_file(
+  ast: &analyzer::SemanticSchemaBlock,
+  up_stmts: Vec<Vec<String>>,
+  down_stmts: Vec<Vec<String>>,
+  idens: &Idens,
+  has_types: bool,
+  config: &Config,
+) -> Codegen {
+  let up_block = Block::new(2, Some("async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr>"));
+  let down_block = Block::new(2, Some("async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr>"));
+
+  let [up_block, down_block] = [(up_block, up_stmts), (down_block, down_stmts)].map(|(block, stmts)| {
+    stmts
+      .iter()
+      .fold(block, |acc, stmt| {
+        stmt.iter().fold(acc, |acc, line| acc.line(line)).line_skip(1)
+      })
+      .line("Ok(())")
+  });
+
+  let codegen = gen_header(ast)
+    .line_skip(1)
+    .line("use sea_orm_migration::prelude::*;")
+    .line_cond(has_types, "use sea_orm_migration::sea_query::extension::postgres::Type;")
+    .line_skip(1)
+    .line("#[derive(DeriveMigrationName)]")
+    .line("pub struct Migration;")
+    .line_skip(1)
+    .line("#[async_trait::async_trait]")
+    .block(
+      Block::new(1, Some("impl MigrationTrait for Migration"))
+        .block(up_block)
+        .line_skip(1)
+        .block(down_block),
+    );
+
+  // identifiers
+  idens.0.iter().fold(codegen, |acc, (name, variants)| {
+    let variants: Vec<_> = variants.iter().map(String::as_str).collect();
+
+    gen_iden_enum(acc, name, &variants, config)
+  })
+}
+
+/// Generates the identifier enum of a table or an enum type, with `Table` standing for the name itself.
+fn gen_iden_enum(codegen: Codegen, name: &str, variants: &[&str], config: &Config) -> Codegen {
+  let enum_block = Block::new(1, Some(format!("enum {}", name.to_pascal_case())));
+  let enum_block = match iden_variant(name, config) {
+    (_, Some(attr)) => enum_block.line(attr).line("Table,"),
+    (_, None) => enum_block.line("Table,"),
+  };
+
+  let enum_block = variants.iter().fold(enum_block, |acc, variant| {
+    let (variant, attr) = iden_variant(variant, config);
+
+    match attr {
+      Some(attr) => acc.line(attr).line(format!("{},", variant)),
+      None => acc.line(format!("{},", variant)),
+    }
+  });
+
+  let derive = match config.sea_orm_version {
+    SeaOrmVersion::V0_11 => "#[derive(Iden)]",
+    _ => "#[derive(DeriveIden)]",
+  };
+
+  codegen.line_skip(1).line(derive).block(enum_block)
+}
@@ -0,0 +1,662 @@
+use std::path::PathBuf;
+
+use crate::compiler::config::*;
+use crate::compiler::diff::*;
+use crate::compiler::err::*;
+use crate::compiler::traits::*;
+use crate::compiler::{ref_location, Output, OutputFile};
+use crate::generator::Codegen;
+use crate::{DEFAULT_SCHEMA, NAME, VERSION};
+
+use dbml_rs::*;
+
+use super::{
+  fk_name, gen_project_note, has_enum_types, index_name, is_auto_increment, is_current_timestamp, is_identity,
+  is_native_enum, order_tables, table_refs, Backend,
+};
+
+/// Backend generating the SQL DDL of the schema for the target database.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sql;
+
+impl Backend for Sql {
+  fn name(&self) -> &str {
+    "sql"
+  }
+
+  fn generate(
+    &self,
+    ast: &analyzer::SemanticSchemaBlock,
+    config: &Config,
+  ) -> Result<Output, CompilerError> {
+    let path = match config.output_mode {
+      OutputMode::File => PathBuf::new(),
+      OutputMode::Directory => PathBuf::from("schema.sql"),
+    };
+
+    Ok(Output {
+      files: vec![OutputFile {
+        path,
+        code: gen_ddl(ast, config)?.to_string(),
+      }],
+      warnings: vec![],
+    })
+  }
+
+  fn generate_diff(
+    &self,
+    diff: &SchemaDiff,
+    _old: &analyzer::SemanticSchemaBlock,
+    new: &analyzer::SemanticSchemaBlock,
+    config: &Config,
+  ) -> Result<Output, CompilerError> {
+    Ok(Output {
+      files: vec![OutputFile {
+        path: PathBuf::new(),
+        code: gen_alter_ddl(diff, new, config)?.to_string(),
+      }],
+      warnings: vec![],
+    })
+  }
+}
+
+/// Quotes an identifier for the target.
+fn quote(name: &str, target: &Target) -> String {
+  match target {
+    Target::MySql => format!("`{}`", name.replace('`', "``")),
+    Target::Postgres | Target::Sqlite => format!("\"{}\"", name.replace('"', "\"\"")),
+  }
+}
+
+/// Quotes a string literal.
+fn quote_str(value: &str) -> String {
+  format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Gets the quoted name of a table or a type, qualified by its schema on Postgres.
+fn quote_ident(name: &str, schema: &Option<String>, target: &Target) -> String {
+  match schema {
+    Some(schema) if target.has_schema() && schema != DEFAULT_SCHEMA => {
+      format!("{}.{}", quote(schema, target), quote(name, target))
+    }
+    _ => quote(name, target),
+  }
+}
+
+/// Gets the SQL type of a column.
+fn gen_type(
+  ast: &analyzer::SemanticSchemaBlock,
+  table: &ast::table::TableIdent,
+  col: &ast::table::TableColumn,
+  config: &Config,
+) -> Result<String, CompilerError> {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+
+  match &col.r#type.type_name {
+    ast::table::ColumnTypeName::Enum(name) if is_native_enum(config) => {
+      let r#enum = ast.enums.iter().find(|r#enum| &r#enum.ident.name == name);
+
+      match (&target, r#enum) {
+        (Target::MySql, Some(r#enum)) => {
+          let values: Vec<_> = r#enum.values.iter().map(|value| quote_str(&value.value)).collect();
+
+          Ok(format!("enum({})", values.join(", ")))
+        }
+        (_, r#enum) => {
+          let schema = r#enum.and_then(|r#enum| r#enum.ident.schema.clone());
+
+          Ok(
+            col
+              .r#type
+              .arrays
+              .iter()
+              .fold(quote_ident(name, &schema, &target), |acc, _| format!("{}[]", acc)),
+          )
+        }
+      }
+    }
+    _ => col
+      .r#type
+      .to_sql_type(config)
+      .map_err(|err| err.at(Location::column(table, col))),
+  }
+}
+
+/// Gets the default value of a column as an SQL expression.
+fn gen_default(value: &ast::table::Value, target: &Target) -> String {
+  match value {
+    // MySQL and SQLite spell the current time as `CURRENT_TIMESTAMP`
+    ast::table::Value::Expr(expr) | ast::table::Value::String(expr)
+      if matches!(target, Target::MySql | Target::Sqlite) && is_current_timestamp(expr) =>
+    {
+      "CURRENT_TIMESTAMP".to_string()
+    }
+    ast::table::Value::String(val) | ast::table::Value::HexColor(val) => quote_str(val),
+    ast::table::Value::Integer(val) => val.to_string(),
+    ast::table::Value::Decimal(val) => val.to_string(),
+    ast::table::Value::Bool(val) => val.to_string().to_uppercase(),
+    ast::table::Value::Expr(val) if *target == Target::Postgres => val.clone(),
+    // the expressions are parenthesized as default values in MySQL and SQLite
+    ast::table::Value::Expr(val) => format!("({})", val),
+    ast::table::Value::Null => "NULL".to_string(),
+  }
+}
+
+/// Generates the definition of a column.
+/// The definition of a modified column leaves out the keys, which are already set on the table.
+fn gen_column(
+  ast: &analyzer::SemanticSchemaBlock,
+  table: &ast::table::TableBlock,
+  col: &ast::table::TableColumn,
+  config: &Config,
+  is_modified: bool,
+) -> Result<String, CompilerError> {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+  let pk_list = &table.meta_indexer.pk_list;
+  let is_pk = pk_list.len() == 1 && pk_list[0] == col.name && !is_modified;
+  let is_auto_increment = is_auto_increment(col);
+  let identity = col.settings.default.as_ref().filter(|default| is_identity(default));
+
+  let sql_type = gen_type(ast, &table.ident, col, config)?;
+
+  // Postgres spells the auto-increment as a type, unless it is an identity
+  let sql_type = match (&target, is_auto_increment && identity.is_none(), sql_type.as_str()) {
+    (Target::Postgres, true, "smallint") => "smallserial".to_string(),
+    (Target::Postgres, true, "integer") => "serial".to_string(),
+    (Target::Postgres, true, "bigint") => "bigserial".to_string(),
+    _ => sql_type,
+  };
+
+  let mut def = format!("{} {}", quote(&col.name, &target), sql_type);
+
+  if !col.settings.is_nullable {
+    def.push_str(" NOT NULL")
+  }
+  if is_pk {
+    def.push_str(" PRIMARY KEY")
+  }
+  if is_auto_increment {
+    match target {
+      Target::MySql => def.push_str(" AUTO_INCREMENT"),
+      Target::Sqlite if is_pk => def.push_str(" AUTOINCREMENT"),
+      _ => (),
+    }
+  }
+  if col.settings.is_unique && !is_modified {
+    def.push_str(" UNIQUE")
+  }
+  match (&col.settings.default, identity) {
+    (_, Some(identity)) if target == Target::Postgres => def.push_str(&format!(" {}", gen_default(identity, &target))),
+    (_, Some(_)) => (),
+    (Some(default), None) => def.push_str(&format!(" DEFAULT {}", gen_default(default, &target))),
+    (None, None) => (),
+  }
+
+  Ok(def)
+}
+
+/// Generates the foreign key constraint of a resolved ref.
+fn gen_fk(ref_block: &ast::refs::RefBlock, target: &Target) -> String {
+  let ast::refs::RefBlock { lhs, rhs, .. } = ref_block;
+
+  let from_cols: Vec<_> = lhs.compositions.iter().map(|col| quote(col, target)).collect();
+  let to_cols: Vec<_> = rhs.compositions.iter().map(|col| quote(col, target)).collect();
+
+  let mut constraint = format!(
+    "CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})",
+    quote(&fk_name(ref_block), target),
+    from_cols.join(", "),
+    quote_ident(&rhs.table, &rhs.schema, target),
+    to_cols.join(", ")
+  );
+
+  if let Some(settings) = &ref_block.settings {
+    if let Some(action) = &settings.on_delete {
+      constraint.push_str(&format!(" ON DELETE {}", action.to_string().to_uppercase()))
+    }
+    if let Some(action) = &settings.on_update {
+      constraint.push_str(&format!(" ON UPDATE {}", action.to_string().to_uppercase()))
+    }
+  }
+
+  constraint
+}
+
+/// Generates the `CREATE TYPE` statement of an enum.
+fn gen_create_type(r#enum: &ast::enums::EnumBlock, target: &Target) -> String {
+  let values: Vec<_> = r#enum.values.iter().map(|value| quote_str(&value.value)).collect();
+
+  format!(
+    "CREATE TYPE {} AS ENUM ({});",
+    quote_ident(&r#enum.ident.name, &r#enum.ident.schema, target),
+    values.join(", ")
+  )
+}
+
+/// Generates the `CREATE TABLE` statement of a table, with the foreign keys of the given refs.
+fn gen_create_table(
+  ast: &analyzer::SemanticSchemaBlock,
+  table: &ast::table::TableBlock,
+  refs: &[ast::refs::RefBlock],
+  config: &Config,
+) -> Result<String, CompilerError> {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+  let ident = &table.ident;
+  let pk_list = &table.meta_indexer.pk_list;
+
+  let mut defs = vec![];
+
+  for col in table.cols.iter() {
+    defs.push(gen_column(ast, table, col, config, false)?);
+  }
+
+  if pk_list.len() > 1 {
+    let cols: Vec<_> = pk_list.iter().map(|col| quote(col, &target)).collect();
+
+    defs.push(format!("PRIMARY KEY ({})", cols.join(", ")));
+  }
+
+  defs.extend(refs.iter().map(|ref_block| gen_fk(ref_block, &target)));
+
+  let defs: Vec<_> = defs.iter().map(|def| format!("\t{}", def)).collect();
+
+  Ok(format!(
+    "CREATE TABLE {} (\n{}\n);",
+    quote_ident(&ident.name, &ident.schema, &target),
+    defs.join(",\n")
+  ))
+}
+
+/// Generates the `CREATE INDEX` statement of an index. The primary keys are declared by the tables instead.
+fn gen_create_index(
+  table: &ast::table::TableIdent,
+  def: &ast::indexes::IndexesDef,
+  target: &Target,
+) -> Option<String> {
+  let settings = def.settings.clone().unwrap_or_default();
+
+  if settings.is_pk {
+    return None;
+  }
+
+  let table_name = quote_ident(&table.name, &table.schema, target);
+  let cols: Vec<_> = def
+    .cols
+    .iter()
+    .map(|col| match col {
+      ast::indexes::IndexesColumnType::String(col) => quote(col, target),
+      ast::indexes::IndexesColumnType::Expr(expr) => format!("({})", expr),
+    })
+    .collect();
+
+  let method = match (target, &settings.r#type) {
+    (Target::Postgres, Some(index_type)) => Some(format!("{:?}", index_type).to_lowercase()),
+    (Target::MySql, Some(ast::indexes::IndexesType::BTree)) => Some("BTREE".to_string()),
+    (Target::MySql, Some(ast::indexes::IndexesType::Hash)) => Some("HASH".to_string()),
+    _ => None,
+  };
+
+  Some(match (target, method) {
+    (Target::Postgres, Some(method)) => format!(
+      "CREATE {}INDEX {} ON {} USING {} ({});",
+      if settings.is_unique { "UNIQUE " } else { "" },
+      quote(&index_name(&table.name, def), target),
+      table_name,
+      method,
+      cols.join(", ")
+    ),
+    (_, method) => format!(
+      "CREATE {}INDEX {} ON {} ({}){};",
+      if settings.is_unique { "UNIQUE " } else { "" },
+      quote(&index_name(&table.name, def), target),
+      table_name,
+      cols.join(", "),
+      method.map(|method| format!(" USING {}", method)).unwrap_or_default()
+    ),
+  })
+}
+
+/// Generates the `CREATE TYPE`, `CREATE TABLE` and `CREATE INDEX` statements, in the order of their dependencies.
+fn gen_ddl(ast: &analyzer::SemanticSchemaBlock, config: &Config) -> Result<Codegen, CompilerError> {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+
+  let codegen = gen_project_note(
+    ast,
+    Codegen::new().line(format!("-- Generated by {NAME} {VERSION}")),
+    "--",
+  );
+
+  let mut stmts: Vec<String> = vec![];
+  let mut fk_stmts: Vec<String> = vec![];
+  let mut index_stmts: Vec<String> = vec![];
+
+  // enum types
+  if has_enum_types(config) {
+    stmts.extend(ast.enums.iter().map(|r#enum| gen_create_type(r#enum, &target)));
+  }
+
+  let order = order_tables(ast);
+  let position = |ident: &ast::refs::RefIdent| {
+    order.iter().position(|i| {
+      let table = &ast.tables[*i].ident;
+
+      table.schema == ident.schema && table.name == ident.table
+    })
+  };
+
+  // tables
+  for (pos, table) in order.iter().map(|i| &ast.tables[*i]).enumerate() {
+    let ident = &table.ident;
+
+    let mut refs = vec![];
+
+    for ref_block in table_refs(ast, ident)? {
+      // the refs to a table that is created later are added afterwards, except on SQLite,
+      // which does not alter constraints and does not check the referenced table on creation
+      let rhs_pos = position(&ref_block.rhs).ok_or_else(|| CompilerError::UnresolvedTable {
+        name: ref_block.rhs.table.clone(),
+        location: Box::new(ref_location(ast, &ref_block.lhs)),
+      })?;
+
+      match rhs_pos > pos && target != Target::Sqlite {
+        true => fk_stmts.push(format!(
+          "ALTER TABLE {} ADD {};",
+          quote_ident(&ident.name, &ident.schema, &target),
+          gen_fk(&ref_block, &target)
+        )),
+        false => refs.push(ref_block),
+      }
+    }
+
+    stmts.push(gen_create_table(ast, table, &refs, config)?);
+
+    // indexes
+    let index_defs = table.indexes.iter().flat_map(|indexes| indexes.defs.iter());
+
+    index_stmts.extend(index_defs.filter_map(|def| gen_create_index(ident, def, &target)));
+  }
+
+  let codegen = [stmts, fk_stmts, index_stmts]
+    .concat()
+    .iter()
+    .fold(codegen, |acc, stmt| acc.line_skip(1).line(stmt));
+
+  Ok(codegen)
+}
+
+/// Generates the statements changing the primary key, the uniqueness and (on Postgres) the auto-increment of a column,
+/// which are left out of the definition of a modified column.
+/// The keys are added before the column is modified and dropped after it, as MySQL requires a key on an auto-increment column.
+/// The constraints are named as the databases name the inline ones.
+pub(super) fn gen_alter_keys(
+  table: &ast::table::TableIdent,
+  old: &ast::table::TableColumn,
+  new: &ast::table::TableColumn,
+  target: &Target,
+) -> (Vec<String>, Vec<String>) {
+  let table_name = quote_ident(&table.name, &table.schema, target);
+  let col_name = quote(&new.name, target);
+  let alter = |action: String| format!("ALTER TABLE {} {};", table_name, action);
+
+  let mut add_keys = vec![];
+  let mut drop_keys = vec![];
+
+  match (old.settings.is_pk, new.settings.is_pk, target) {
+    (false, true, _) => add_keys.push(alter(format!("ADD PRIMARY KEY ({})", col_name))),
+    (true, false, Target::MySql) => drop_keys.push(alter("DROP PRIMARY KEY".to_string())),
+    (true, false, _) => drop_keys.push(alter(format!(
+      "DROP CONSTRAINT {}",
+      quote(&format!("{}_pkey", table.name), target)
+    ))),
+    _ => (),
+  }
+
+  let unique_name = match target {
+    Target::MySql => quote(&new.name, target),
+    _ => quote(&format!("{}_{}_key", table.name, new.name), target),
+  };
+
+  match (old.settings.is_unique, new.settings.is_unique, target) {
+    (false, true, Target::MySql) => add_keys.push(alter(format!("ADD UNIQUE INDEX {} ({})", unique_name, col_name))),
+    (false, true, _) => add_keys.push(alter(format!("ADD CONSTRAINT {} UNIQUE ({})", unique_name, col_name))),
+    (true, false, Target::MySql) => drop_keys.push(alter(format!("DROP INDEX {}", unique_name))),
+    (true, false, _) => drop_keys.push(alter(format!("DROP CONSTRAINT {}", unique_name))),
+    _ => (),
+  }
+
+  // Postgres spells the auto-increment as a type, whose sequence is set as the default value of the column
+  let has_identity = [old, new]
+    .iter()
+    .any(|col| matches!(&col.settings.default, Some(default) if is_identity(default)));
+
+  if *target == Target::Postgres && !has_identity && is_auto_increment(old) != is_auto_increment(new) {
+    let seq_name = quote_ident(&format!("{}_{}_seq", table.name, new.name), &table.schema, target);
+
+    match is_auto_increment(new) {
+      true => add_keys.extend([
+        format!("CREATE SEQUENCE {} OWNED BY {}.{};", seq_name, table_name, col_name),
+        alter(format!("ALTER COLUMN {} SET DEFAULT nextval({})", col_name, quote_str(&seq_name))),
+      ]),
+      false => drop_keys.extend([
+        alter(format!("ALTER COLUMN {} DROP DEFAULT", col_name)),
+        format!("DROP SEQUENCE {};", seq_name),
+      ]),
+    }
+  }
+
+  (add_keys, drop_keys)
+}
+
+/// Generates the statements applying the changes of a diff, with the new version of the schema.
+fn gen_alter_ddl(
+  diff: &SchemaDiff,
+  ast: &analyzer::SemanticSchemaBlock,
+  config: &Config,
+) -> Result<Codegen, CompilerError> {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+
+  let codegen = gen_project_note(
+    ast,
+    Codegen::new().line(format!("-- Generated by {NAME} {VERSION}")),
+    "--",
+  );
+
+  let find_table = |ident: &ast::table::TableIdent| {
+    ast
+      .tables
+      .iter()
+      .find(|table| table.ident.name == ident.name && table.ident.schema == ident.schema)
+      .ok_or_else(|| CompilerError::UnresolvedTable {
+        name: ident.name.clone(),
+        location: Box::new(Location::table_ident(ident)),
+      })
+  };
+  let unsupported = |feature: &str, location: Location| CompilerError::UnsupportedFeature {
+    feature: feature.to_string(),
+    target: target.clone(),
+    location: Box::new(location),
+  };
+
+  let mut stmts: Vec<String> = vec![];
+
+  for change in diff.changes.iter() {
+    match change {
+      Change::CreateEnum(r#enum) if has_enum_types(config) => stmts.push(gen_create_type(r#enum, &target)),
+      Change::DropEnum(r#enum) if has_enum_types(config) => stmts.push(format!(
+        "DROP TYPE {};",
+        quote_ident(&r#enum.ident.name, &r#enum.ident.schema, &target)
+      )),
+      Change::AddEnumValue { r#enum, value } if has_enum_types(config) => stmts.push(format!(
+        "ALTER TYPE {} ADD VALUE {};",
+        quote_ident(&r#enum.name, &r#enum.schema, &target),
+        quote_str(value)
+      )),
+      Change::DropEnumValue { r#enum, .. } if has_enum_types(config) => {
+        return Err(unsupported(
+          "dropping an enum value",
+          Location::enum_block(r#enum),
+        ))
+      }
+      // the inline enums of MySQL are changed along with the columns using them
+      Change::AddEnumValue { r#enum, .. } | Change::DropEnumValue { r#enum, .. } if is_native_enum(config) => {
+        for table in ast.tables.iter() {
+          if diff.is_created(&table.ident.name, &table.ident.schema) {
+            continue;
+          }
+
+          for col in table.cols.iter() {
+            if col.r#type.type_name != ast::table::ColumnTypeName::Enum(r#enum.name.clone()) {
+              continue;
+            }
+
+            let stmt = format!(
+              "ALTER TABLE {} MODIFY COLUMN {};",
+              quote_ident(&table.ident.name, &table.ident.schema, &target),
+              gen_column(ast, table, col, config, true)?
+            );
+
+            if !stmts.contains(&stmt) {
+              stmts.push(stmt)
+            }
+          }
+        }
+      }
+      Change::CreateEnum(_) | Change::DropEnum(_) | Change::AddEnumValue { .. } | Change::DropEnumValue { .. } => (),
+      Change::DropRef(ref_block) => {
+        let lhs = &ref_block.lhs;
+        let table_name = quote_ident(&lhs.table, &lhs.schema, &target);
+        let name = quote(&fk_name(ref_block), &target);
+
+        stmts.push(match target {
+          Target::Postgres => format!("ALTER TABLE {} DROP CONSTRAINT {};", table_name, name),
+          Target::MySql => format!("ALTER TABLE {} DROP FOREIGN KEY {};", table_name, name),
+          Target::Sqlite => {
+            return Err(unsupported(
+              "dropping a foreign key",
+              ref_location(ast, lhs),
+            ))
+          }
+        })
+      }
+      Change::DropIndex { table, index } => {
+        let name = index_name(&table.name, index);
+
+        stmts.push(match target {
+          Target::Postgres => format!("DROP INDEX {};", quote_ident(&name, &table.schema, &target)),
+          Target::MySql => format!(
+            "DROP INDEX {} ON {};",
+            quote(&name, &target),
+            quote_ident(&table.name, &table.schema, &target)
+          ),
+          Target::Sqlite => format!("DROP INDEX {};", quote(&name, &target)),
+        })
+      }
+      Change::DropTable(table) => stmts.push(format!(
+        "DROP TABLE {};",
+        quote_ident(&table.ident.name, &table.ident.schema, &target)
+      )),
+      Change::CreateTable(table) => {
+        // SQLite creates every foreign key along with the table, as it does not alter constraints
+        let refs: Vec<_> = table_refs(ast, &table.ident)?
+          .into_iter()
+          .filter(|ref_block| {
+            target == Target::Sqlite || !diff.changes.contains(&Change::AddRef(ref_block.clone()))
+          })
+          .collect();
+
+        stmts.push(gen_create_table(ast, table, &refs, config)?);
+
+        let index_defs = table.indexes.iter().flat_map(|indexes| indexes.defs.iter());
+
+        stmts.extend(index_defs.filter_map(|def| gen_create_index(&table.ident, def, &target)));
+      }
+      Change::AddColumn { table, column } => stmts.push(format!(
+        "ALTER TABLE {} ADD COLUMN {};",
+        quote_ident(&table.name, &table.schema, &target),
+        gen_column(ast, find_table(table)?, column, config, false)?
+      )),
+      Change::AlterColumn { table, old, new } => {
+        let table_name = quote_ident(&table.name, &table.schema, &target);
+
+        if target == Target::Sqlite {
+          return Err(unsupported(
+            "altering a column",
+            Location::column(table, new),
+          ));
+        }
+
+        let (add_keys, drop_keys) = gen_alter_keys(table, old, new, &target);
+
+        stmts.extend(add_keys);
+
+        match target {
+          Target::Postgres => {
+            let prefix = format!("ALTER TABLE {} ALTER COLUMN {}", table_name, quote(&new.name, &target));
+
+            if old.r#type.type_name != new.r#type.type_name
+              || old.r#type.args != new.r#type.args
+              || old.r#type.arrays != new.r#type.arrays
+            {
+              stmts.push(format!("{} TYPE {};", prefix, gen_type(ast, table, new, config)?))
+            }
+            if old.settings.is_nullable != new.settings.is_nullable {
+              stmts.push(match new.settings.is_nullable {
+                true => format!("{} DROP NOT NULL;", prefix),
+                false => format!("{} SET NOT NULL;", prefix),
+              })
+            }
+            if old.settings.default != new.settings.default {
+              stmts.push(match &new.settings.default {
+                Some(default) => format!("{} SET DEFAULT {};", prefix, gen_default(default, &target)),
+                None => format!("{} DROP DEFAULT;", prefix),
+              })
+            }
+          }
+          // MySQL spells the auto-increment in the definition of the column
+          Target::MySql
+            if is_definition_changed(old, new) || old.settings.is_incremental != new.settings.is_incremental =>
+          {
+            stmts.push(format!(
+              "ALTER TABLE {} MODIFY COLUMN {};",
+              table_name,
+              gen_column(ast, find_table(table)?, new, config, true)?
+            ))
+          }
+          Target::MySql | Target::Sqlite => (),
+        }
+
+        stmts.extend(drop_keys);
+      }
+      Change::DropColumn { table, column } => stmts.push(format!(
+        "ALTER TABLE {} DROP COLUMN {};",
+        quote_ident(&table.name, &table.schema, &target),
+        quote(&column.name, &target)
+      )),
+      Change::AddRef(ref_block) => {
+        let lhs = &ref_block.lhs;
+
+        match target {
+          Target::Sqlite if diff.is_created(&lhs.table, &lhs.schema) => (),
+          Target::Sqlite => {
+            return Err(unsupported(
+              "adding a foreign key",
+              ref_location(ast, lhs),
+            ))
+          }
+          _ => stmts.push(format!(
+            "ALTER TABLE {} ADD {};",
+            quote_ident(&lhs.table, &lhs.schema, &target),
+            gen_fk(ref_block, &target)
+          )),
+        }
+      }
+      Change::AddIndex { table, index } => stmts.extend(gen_create_index(table, index, &target)),
+    }
+  }
+
+  let codegen = stmts
+    .iter()
+    .fold(codegen, |acc, stmt| acc.line_skip(1).line(stmt));
+
+  Ok(codegen)
+}
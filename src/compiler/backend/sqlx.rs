@@ -0,0 +1,200 @@
+use std::path::PathBuf;
+
+use crate::compiler::config::*;
+use crate::compiler::err::*;
+use crate::compiler::traits::*;
+use crate::compiler::{Output, OutputFile};
+use crate::generator::{Block, Codegen};
+use crate::DEFAULT_SCHEMA;
+
+use inflector::Inflector;
+
+use dbml_rs::*;
+
+use super::{check_array_dimensions, gen_header, Backend};
+
+/// Backend generating plain structs deriving `sqlx::FromRow`, along with the enums deriving `sqlx::Type`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sqlx;
+
+impl Backend for Sqlx {
+  fn name(&self) -> &str {
+    "sqlx"
+  }
+
+  fn generate(
+    &self,
+    ast: &analyzer::SemanticSchemaBlock,
+    config: &Config,
+  ) -> Result<Output, CompilerError> {
+    check_array_dimensions(ast, config)?;
+
+    let files = match config.output_mode {
+      OutputMode::File => {
+        let codegen = ast.tables.iter().try_fold(gen_header(ast), |acc, table| {
+          gen_struct(table, acc.line_skip(1), config)
+        })?;
+
+        vec![OutputFile {
+          path: PathBuf::new(),
+          code: gen_enums(ast, codegen, config).to_string(),
+        }]
+      }
+      OutputMode::Directory => {
+        let header = gen_header(ast);
+
+        let mod_names: Vec<_> = ast
+          .tables
+          .iter()
+          .map(|table| config.naming.module_name(&table.ident.name))
+          .collect();
+
+        let mod_codegen = mod_names
+          .iter()
+          .fold(header.clone().line_skip(1), |acc, name| {
+            acc.line(format!("pub mod {};", name))
+          })
+          .line_cond(!ast.enums.is_empty(), "pub mod enums;");
+
+        let mut files = vec![OutputFile {
+          path: PathBuf::from("mod.rs"),
+          code: mod_codegen.to_string(),
+        }];
+
+        for (table, name) in ast.tables.iter().zip(mod_names.iter()) {
+          files.push(OutputFile {
+            path: PathBuf::from(format!("{}.rs", name)),
+            code: gen_struct(table, header.clone().line_skip(1), config)?.to_string(),
+          })
+        }
+
+        if !ast.enums.is_empty() {
+          files.push(OutputFile {
+            path: PathBuf::from("enums.rs"),
+            code: gen_enums(ast, header, config).to_string(),
+          })
+        }
+
+        files
+      }
+    };
+
+    Ok(Output {
+      files,
+      warnings: vec![],
+    })
+  }
+}
+
+/// Generates the `FromRow` struct of a table.
+fn gen_struct(
+  table: &ast::table::TableBlock,
+  codegen: Codegen,
+  config: &Config,
+) -> Result<Codegen, CompilerError> {
+  let ident = &table.ident;
+
+  let enum_mod = match config.output_mode {
+    OutputMode::File => "",
+    OutputMode::Directory => "super::enums::",
+  };
+
+  let struct_block = Block::new(1, Some(format!("pub struct {}", config.naming.table_name(&ident.name).to_class_case())));
+
+  let struct_block = table.cols.iter().try_fold(struct_block, |acc, col| {
+    let rust_type = col
+      .r#type
+      .to_rust_type(config)
+      .map_err(|err| err.at(Location::column(ident, col)))?;
+
+    // the enums are generated next to the structs rather than as SeaORM active enums
+    let rust_type = match &col.r#type.type_name {
+      ast::table::ColumnTypeName::Enum(name) => array_rust_type(
+        format!("{}{}", enum_mod, name.to_pascal_case()),
+        &col.r#type.arrays,
+      ),
+      type_name => match to_pg_row_type(type_name) {
+        Some(row_type) => array_rust_type(row_type.to_string(), &col.r#type.arrays),
+        None => qualify_rust_type(&rust_type),
+      },
+    };
+    let rust_type = annotated_rust_type(col).unwrap_or(rust_type);
+    let rust_type = match col.settings.is_nullable {
+      true => format!("Option<{}>", rust_type),
+      false => rust_type,
+    };
+    let field_name = col.name.to_snake_case();
+
+    Ok::<_, CompilerError>(
+      acc
+        .line_cond(
+          field_name != col.name,
+          format!(r#"#[sqlx(rename = "{}")]"#, col.name),
+        )
+        .line(format!("pub {}: {},", field_name, rust_type)),
+    )
+  })?;
+
+  Ok(
+    codegen
+      .line("#[derive(Clone, Debug, PartialEq, sqlx::FromRow)]")
+      .block(struct_block),
+  )
+}
+
+/// Generates the enums deriving `sqlx::Type`, stored in the same way as the SeaORM active enums.
+fn gen_enums(ast: &analyzer::SemanticSchemaBlock, codegen: Codegen, config: &Config) -> Codegen {
+  let target = config.target.clone().unwrap_or(Target::Postgres);
+
+  ast.enums.iter().fold(codegen, |acc, r#enum| {
+    let ast::enums::EnumIdent { name, schema } = &r#enum.ident;
+
+    let type_name = match (&config.enum_type, &target) {
+      (EnumType::Integer, _) => None,
+      // the type name is only checked by Postgres
+      (EnumType::String(_), Target::Postgres) if config.is_native_enum => match schema {
+        Some(schema) if schema != DEFAULT_SCHEMA => Some(format!("{}.{}", schema, name)),
+        _ => Some(name.clone()),
+      },
+      (EnumType::String(Some(_)), Target::Postgres) => Some("varchar".to_string()),
+      (EnumType::String(None), Target::Postgres) => Some("text".to_string()),
+      (EnumType::String(_), _) => None,
+    };
+
+    let enum_block = Block::new(1, Some(format!("pub enum {}", name.to_pascal_case())));
+
+    let enum_block = r#enum.values.iter().enumerate().fold(enum_block, |acc, (i, value)| {
+      let value_name = &value.value;
+
+      match config.enum_type {
+        EnumType::Integer => acc.line(format!("{} = {},", value_name.to_pascal_case(), i)),
+        EnumType::String(_) => acc
+          .line(format!(r#"#[sqlx(rename = "{}")]"#, value_name))
+          .line(format!("{},", value_name.to_pascal_case())),
+      }
+    });
+
+    acc
+      .line_skip(1)
+      .line("#[derive(Clone, Copy, Debug, PartialEq, Eq, sqlx::Type)]")
+      .line_cond(config.enum_type == EnumType::Integer, "#[repr(i32)]")
+      .line_cond(
+        type_name.is_some(),
+        format!(r#"#[sqlx(type_name = "{}")]"#, type_name.unwrap_or_default()),
+      )
+      .block(enum_block)
+  })
+}
+
+/// Gets the Rust type of the Postgres types that SQLx decodes into types of its own.
+fn to_pg_row_type(type_name: &ast::table::ColumnTypeName) -> Option<&'static str> {
+  match type_name {
+    ast::table::ColumnTypeName::Money => Some("sqlx::postgres::types::PgMoney"),
+    ast::table::ColumnTypeName::Inet | ast::table::ColumnTypeName::Cidr => Some("ipnetwork::IpNetwork"),
+    ast::table::ColumnTypeName::MacAddr => Some("mac_address::MacAddress"),
+    ast::table::ColumnTypeName::Timetz => Some("sqlx::postgres::types::PgTimeTz"),
+    ast::table::ColumnTypeName::Bit | ast::table::ColumnTypeName::Varbit => Some("bit_vec::BitVec"),
+    ast::table::ColumnTypeName::Raw(name) if name == "interval" => Some("sqlx::postgres::types::PgInterval"),
+    _ => None,
+  }
+}
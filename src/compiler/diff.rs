@@ -0,0 +1,321 @@
+use crate::DEFAULT_SCHEMA;
+
+use dbml_rs::*;
+
+use super::backend::order_tables;
+
+/// Change from the old version of a schema to the new one.
+///
+/// The refs are the ones backed by a foreign key, resolved from their aliases and written from the referencing side.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Change {
+  CreateEnum(ast::enums::EnumBlock),
+  AddEnumValue {
+    r#enum: ast::enums::EnumIdent,
+    value: String,
+  },
+  DropRef(ast::refs::RefBlock),
+  DropIndex {
+    table: ast::table::TableIdent,
+    index: ast::indexes::IndexesDef,
+  },
+  DropTable(ast::table::TableBlock),
+  /// The refs of a created table are created along with it, unless they point to a table created later.
+  CreateTable(ast::table::TableBlock),
+  AddColumn {
+    table: ast::table::TableIdent,
+    column: ast::table::TableColumn,
+  },
+  /// The type, the nullability, the default value, the primary key, the uniqueness or the auto-increment
+  /// of the column is changed.
+  AlterColumn {
+    table: ast::table::TableIdent,
+    old: Box<ast::table::TableColumn>,
+    new: Box<ast::table::TableColumn>,
+  },
+  DropColumn {
+    table: ast::table::TableIdent,
+    column: ast::table::TableColumn,
+  },
+  AddRef(ast::refs::RefBlock),
+  AddIndex {
+    table: ast::table::TableIdent,
+    index: ast::indexes::IndexesDef,
+  },
+  DropEnumValue {
+    r#enum: ast::enums::EnumIdent,
+    value: String,
+  },
+  DropEnum(ast::enums::EnumBlock),
+}
+
+/// Changes between two versions of a schema, in the order they are applied.
+///
+/// The tables, columns and enums are matched by name, so a renaming is seen as a drop and a creation.
+/// The primary keys are compared by the `pk` setting of the columns, so the changes of the composite ones are not detected.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct SchemaDiff {
+  pub changes: Vec<Change>,
+}
+
+impl SchemaDiff {
+  pub fn is_empty(&self) -> bool {
+    self.changes.is_empty()
+  }
+
+  /// Checks whether the table is created by the changes.
+  pub fn is_created(&self, table: &str, schema: &Option<String>) -> bool {
+    self.changes.iter().any(|change| match change {
+      Change::CreateTable(created) => is_same(&created.ident.name, &created.ident.schema, table, schema),
+      _ => false,
+    })
+  }
+}
+
+/// Compares the old version of a schema with the new one.
+/// Both schemas are expected to be normalized in the same way as the ones given to the backends.
+pub fn diff(old: &analyzer::SemanticSchemaBlock, new: &analyzer::SemanticSchemaBlock) -> SchemaDiff {
+  let find_table = |ast: &analyzer::SemanticSchemaBlock, name: &str, schema: &Option<String>| {
+    ast
+      .tables
+      .iter()
+      .position(|table| is_same(&table.ident.name, &table.ident.schema, name, schema))
+  };
+
+  let old_refs = fk_refs(old);
+  let new_refs = fk_refs(new);
+
+  // the tables are dropped in the reverse order of their dependencies, and created in the order of them
+  let dropped: Vec<_> = order_tables(old)
+    .into_iter()
+    .rev()
+    .filter(|i| find_table(new, &old.tables[*i].ident.name, &old.tables[*i].ident.schema).is_none())
+    .collect();
+  let created: Vec<_> = order_tables(new)
+    .into_iter()
+    .filter(|i| find_table(old, &new.tables[*i].ident.name, &new.tables[*i].ident.schema).is_none())
+    .collect();
+  let kept: Vec<_> = new
+    .tables
+    .iter()
+    .filter_map(|table| {
+      find_table(old, &table.ident.name, &table.ident.schema).map(|i| (&old.tables[i], table))
+    })
+    .collect();
+
+  let drop_position = |ident: &ast::refs::RefIdent| {
+    dropped
+      .iter()
+      .position(|i| is_same(&old.tables[*i].ident.name, &old.tables[*i].ident.schema, &ident.table, &ident.schema))
+  };
+  let create_position = |ident: &ast::refs::RefIdent| {
+    created
+      .iter()
+      .position(|i| is_same(&new.tables[*i].ident.name, &new.tables[*i].ident.schema, &ident.table, &ident.schema))
+  };
+
+  let mut changes = vec![];
+
+  // new enums and values, before the tables using them
+  for r#enum in new.enums.iter() {
+    match find_enum(old, &r#enum.ident) {
+      Some(old_enum) => {
+        for value in r#enum.values.iter() {
+          if !old_enum.values.iter().any(|old_value| old_value.value == value.value) {
+            changes.push(Change::AddEnumValue {
+              r#enum: r#enum.ident.clone(),
+              value: value.value.clone(),
+            })
+          }
+        }
+      }
+      None => changes.push(Change::CreateEnum(r#enum.clone())),
+    }
+  }
+
+  // the refs are dropped before their columns and tables, but the ones of a dropped table go along with it,
+  // unless it is dropped after the referenced table, as in a reference cycle
+  for ref_block in old_refs.iter() {
+    let is_dropped_along = match (drop_position(&ref_block.lhs), drop_position(&ref_block.rhs)) {
+      (Some(lhs), Some(rhs)) => lhs <= rhs,
+      (Some(_), None) => true,
+      (None, _) => false,
+    };
+
+    if !is_dropped_along && !new_refs.contains(ref_block) {
+      changes.push(Change::DropRef(ref_block.clone()))
+    }
+  }
+
+  for (old_table, new_table) in kept.iter() {
+    let new_defs = index_defs(new_table);
+
+    for def in index_defs(old_table) {
+      if !new_defs.contains(&def) {
+        changes.push(Change::DropIndex {
+          table: new_table.ident.clone(),
+          index: def,
+        })
+      }
+    }
+  }
+
+  for i in dropped.iter() {
+    changes.push(Change::DropTable(old.tables[*i].clone()))
+  }
+
+  for i in created.iter() {
+    changes.push(Change::CreateTable(new.tables[*i].clone()))
+  }
+
+  for (old_table, new_table) in kept.iter() {
+    let table = &new_table.ident;
+    let mut col_changes = vec![];
+
+    for col in new_table.cols.iter() {
+      match old_table.cols.iter().find(|old_col| old_col.name == col.name) {
+        Some(old_col) if is_altered(old_col, col) => col_changes.push(Change::AlterColumn {
+          table: table.clone(),
+          old: Box::new(old_col.clone()),
+          new: Box::new(col.clone()),
+        }),
+        Some(_) => (),
+        None => col_changes.push(Change::AddColumn {
+          table: table.clone(),
+          column: col.clone(),
+        }),
+      }
+    }
+
+    // the primary key is dropped from its old column before it is set on the new one
+    col_changes.sort_by_key(|change| {
+      !matches!(change, Change::AlterColumn { old, new, .. } if old.settings.is_pk && !new.settings.is_pk)
+    });
+    changes.extend(col_changes);
+
+    for col in old_table.cols.iter() {
+      if !new_table.cols.iter().any(|new_col| new_col.name == col.name) {
+        changes.push(Change::DropColumn {
+          table: table.clone(),
+          column: col.clone(),
+        })
+      }
+    }
+  }
+
+  // the refs of a created table are created along with it, unless the referenced table is created later
+  for ref_block in new_refs.iter() {
+    let is_created_along = match (create_position(&ref_block.lhs), create_position(&ref_block.rhs)) {
+      (Some(lhs), Some(rhs)) => lhs >= rhs,
+      (Some(_), None) => true,
+      (None, _) => false,
+    };
+
+    if !is_created_along && !old_refs.contains(ref_block) {
+      changes.push(Change::AddRef(ref_block.clone()))
+    }
+  }
+
+  for (old_table, new_table) in kept.iter() {
+    let old_defs = index_defs(old_table);
+
+    for def in index_defs(new_table) {
+      if !old_defs.contains(&def) {
+        changes.push(Change::AddIndex {
+          table: new_table.ident.clone(),
+          index: def,
+        })
+      }
+    }
+  }
+
+  // the old enums and values, once the tables no longer use them
+  for r#enum in old.enums.iter() {
+    match find_enum(new, &r#enum.ident) {
+      Some(new_enum) => {
+        for value in r#enum.values.iter() {
+          if !new_enum.values.iter().any(|new_value| new_value.value == value.value) {
+            changes.push(Change::DropEnumValue {
+              r#enum: r#enum.ident.clone(),
+              value: value.value.clone(),
+            })
+          }
+        }
+      }
+      None => changes.push(Change::DropEnum(r#enum.clone())),
+    }
+  }
+
+  SchemaDiff { changes }
+}
+
+/// Gets the refs backed by a foreign key, with their aliases resolved.
+pub fn fk_refs(ast: &analyzer::SemanticSchemaBlock) -> Vec<ast::refs::RefBlock> {
+  ast
+    .refs
+    .iter()
+    .filter(|ref_block| {
+      matches!(
+        ref_block.rel,
+        ast::refs::Relation::Many2One | ast::refs::Relation::One2One
+      )
+    })
+    .map(|ref_block| ast::refs::RefBlock {
+      lhs: ast.indexer.refer_ref_alias(&ref_block.lhs),
+      rhs: ast.indexer.refer_ref_alias(&ref_block.rhs),
+      rel: ref_block.rel.clone(),
+      settings: ref_block.settings.clone(),
+    })
+    .collect()
+}
+
+/// Finds an enum by its name and schema.
+fn find_enum<'a>(
+  ast: &'a analyzer::SemanticSchemaBlock,
+  ident: &ast::enums::EnumIdent,
+) -> Option<&'a ast::enums::EnumBlock> {
+  ast
+    .enums
+    .iter()
+    .find(|r#enum| is_same(&r#enum.ident.name, &r#enum.ident.schema, &ident.name, &ident.schema))
+}
+
+/// Checks whether two names refer to the same table or enum, the schema being `public` when it is omitted.
+fn is_same(name: &str, schema: &Option<String>, other_name: &str, other_schema: &Option<String>) -> bool {
+  name == other_name
+    && schema.as_deref().unwrap_or(DEFAULT_SCHEMA) == other_schema.as_deref().unwrap_or(DEFAULT_SCHEMA)
+}
+
+/// Gets the index definitions of a table compared between the versions, without their notes and primary keys.
+fn index_defs(table: &ast::table::TableBlock) -> Vec<ast::indexes::IndexesDef> {
+  table
+    .indexes
+    .iter()
+    .flat_map(|indexes| indexes.defs.iter())
+    .filter(|def| !matches!(&def.settings, Some(settings) if settings.is_pk))
+    .map(|def| ast::indexes::IndexesDef {
+      settings: Some(ast::indexes::IndexesSettings {
+        note: None,
+        ..def.settings.clone().unwrap_or_default()
+      }),
+      ..def.clone()
+    })
+    .collect()
+}
+
+/// Checks whether the type, the nullability, the default value, the auto-increment or the keys of a column are changed.
+fn is_altered(old: &ast::table::TableColumn, new: &ast::table::TableColumn) -> bool {
+  is_definition_changed(old, new)
+    || old.settings.is_incremental != new.settings.is_incremental
+    || old.settings.is_pk != new.settings.is_pk
+    || old.settings.is_unique != new.settings.is_unique
+}
+
+/// Checks whether the type, the nullability or the default value of a column is changed.
+pub fn is_definition_changed(old: &ast::table::TableColumn, new: &ast::table::TableColumn) -> bool {
+  old.r#type.type_name != new.r#type.type_name
+    || old.r#type.args != new.r#type.args
+    || old.r#type.arrays != new.r#type.arrays
+    || old.settings.is_nullable != new.settings.is_nullable
+    || old.settings.default != new.settings.default
+}
@@ -1 +1,257 @@
-pub enum CompilerError {}
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::ops::Range;
+
+use dbml_rs::ast;
+
+use super::config::Target;
+use super::utils;
+
+/// Location of a construct in the DBML source.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Location {
+  /// Keyword of the block that contains the construct (e.g. `table` or `tablegroup`).
+  pub keyword: &'static str,
+  /// Schema of the block that contains the construct.
+  pub schema: Option<String>,
+  /// Name of the block that contains the construct.
+  pub name: Option<String>,
+  /// Name of the column that contains the construct.
+  pub column: Option<String>,
+  /// Byte range of the construct in the DBML source.
+  pub span: Option<Range<usize>>,
+  /// Line and column numbers (1-based). Available once resolved against the source.
+  pub line_col: Option<(usize, usize)>,
+}
+
+impl Location {
+  pub fn block(keyword: &'static str, name: impl ToString) -> Self {
+    Self {
+      keyword,
+      name: Some(name.to_string()),
+      ..Default::default()
+    }
+  }
+
+  pub fn table(table: &ast::table::TableBlock) -> Self {
+    Self {
+      span: span(&table.span_range),
+      ..Self::table_ident(&table.ident)
+    }
+  }
+
+  /// Locates a table by its name, for the constructs known by their identifier only.
+  pub fn table_ident(ident: &ast::table::TableIdent) -> Self {
+    Self {
+      schema: ident.schema.clone(),
+      span: span(&ident.span_range),
+      ..Self::block("table", &ident.name)
+    }
+  }
+
+  pub fn column(table: &ast::table::TableIdent, col: &ast::table::TableColumn) -> Self {
+    Self {
+      column: Some(col.name.clone()),
+      span: span(&col.span_range),
+      ..Self::table_ident(table)
+    }
+  }
+
+  pub fn enum_block(ident: &ast::enums::EnumIdent) -> Self {
+    Self {
+      schema: ident.schema.clone(),
+      ..Self::block("enum", &ident.name)
+    }
+  }
+
+  pub fn line_col(line: usize, col: usize) -> Self {
+    Self {
+      line_col: Some((line, col)),
+      ..Default::default()
+    }
+  }
+
+  /// Computes the line and column numbers of the construct in the given source.
+  /// The blocks without a span (e.g. the enums) are looked up by their schema and name.
+  pub fn resolve(&mut self, input: &str) {
+    if self.line_col.is_some() {
+      return;
+    }
+
+    let offset = match (&self.span, &self.name) {
+      (Some(span), _) => Some(span.start),
+      (None, Some(name)) => utils::scan_declarations(input)
+        .into_iter()
+        .find(|decl| decl.keyword == self.keyword && decl.schema == self.schema && &decl.name == name)
+        .map(|decl| {
+          let col = decl
+            .columns
+            .iter()
+            .find(|col| Some(&col.name) == self.column.as_ref());
+
+          col.map_or(decl.span.start, |col| col.span.start)
+        }),
+      (None, None) => None,
+    };
+
+    self.line_col = offset.map(|offset| utils::line_col(input, offset));
+  }
+}
+
+/// Gets the span of an AST node, which is empty when the node is not located.
+fn span(span_range: &Range<usize>) -> Option<Range<usize>> {
+  Some(span_range.clone()).filter(|span| !span.is_empty())
+}
+
+impl fmt::Display for Location {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match (&self.line_col, &self.name, &self.column) {
+      (Some((line, col)), _, _) => write!(f, "{}:{}", line, col),
+      (None, Some(name), Some(column)) => {
+        write!(f, "{} '{}', column '{}'", self.keyword, name, column)
+      }
+      (None, Some(name), None) => write!(f, "{} '{}'", self.keyword, name),
+      _ => write!(f, "unknown location"),
+    }
+  }
+}
+
+#[derive(Debug)]
+pub enum CompilerError {
+  /// The source is rejected by the DBML parser or analyzer.
+  Parse { message: String, location: Box<Location> },
+  /// The column type cannot be represented in the generated code.
+  UnsupportedType { type_name: String, location: Box<Location> },
+  /// The construct is not supported by the target database.
+  UnsupportedFeature {
+    feature: String,
+    target: Target,
+    location: Box<Location>,
+  },
+  /// The relation kind cannot be represented in the generated code.
+  UnsupportedRelation { relation: String, location: Box<Location> },
+  /// The referred table cannot be found.
+  UnresolvedTable { name: String, location: Box<Location> },
+  /// The referred column cannot be found.
+  UnresolvedColumn { name: String, location: Box<Location> },
+  /// The name (e.g. of a column) is declared more than once.
+  Duplicated {
+    kind: &'static str,
+    name: String,
+    location: Box<Location>,
+  },
+  /// The primary key is declared in a way it cannot be used.
+  InvalidPrimaryKey { message: String, location: Box<Location> },
+  /// The ref does not match the columns it refers to.
+  InvalidRef { message: String, location: Box<Location> },
+  /// The configuration is invalid.
+  Config { message: String },
+  /// Reading or writing a file is failed.
+  Io(io::Error),
+}
+
+impl CompilerError {
+  /// Gets the location of the error in the DBML source (if any).
+  pub fn location(&self) -> Option<&Location> {
+    match self {
+      Self::Parse { location, .. }
+      | Self::UnsupportedType { location, .. }
+      | Self::UnsupportedFeature { location, .. }
+      | Self::UnsupportedRelation { location, .. }
+      | Self::UnresolvedTable { location, .. }
+      | Self::UnresolvedColumn { location, .. }
+      | Self::Duplicated { location, .. }
+      | Self::InvalidPrimaryKey { location, .. }
+      | Self::InvalidRef { location, .. } => Some(&**location),
+      Self::Config { .. } | Self::Io(_) => None,
+    }
+  }
+
+  fn location_mut(&mut self) -> Option<&mut Location> {
+    match self {
+      Self::Parse { location, .. }
+      | Self::UnsupportedType { location, .. }
+      | Self::UnsupportedFeature { location, .. }
+      | Self::UnsupportedRelation { location, .. }
+      | Self::UnresolvedTable { location, .. }
+      | Self::UnresolvedColumn { location, .. }
+      | Self::Duplicated { location, .. }
+      | Self::InvalidPrimaryKey { location, .. }
+      | Self::InvalidRef { location, .. } => Some(&mut **location),
+      Self::Config { .. } | Self::Io(_) => None,
+    }
+  }
+
+  /// Sets the location of the error, unless it is already known.
+  pub fn at(mut self, new_location: Location) -> Self {
+    if let Some(location) = self.location_mut() {
+      if *location == Location::default() {
+        *location = new_location
+      }
+    }
+
+    self
+  }
+
+  /// Resolves the line and column numbers of the error against the given source.
+  pub fn with_source(mut self, input: &str) -> Self {
+    if let Some(location) = self.location_mut() {
+      location.resolve(input)
+    }
+
+    self
+  }
+}
+
+impl fmt::Display for CompilerError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Parse { message, location } => write!(f, "{} ({})", message, location),
+      Self::UnsupportedType {
+        type_name,
+        location,
+      } => write!(f, "unsupported column type '{}' ({})", type_name, location),
+      Self::UnsupportedFeature {
+        feature,
+        target,
+        location,
+      } => write!(f, "{} is not supported by {} ({})", feature, target, location),
+      Self::UnsupportedRelation { relation, location } => {
+        write!(f, "unsupported relation '{}' ({})", relation, location)
+      }
+      Self::UnresolvedTable { name, location } => {
+        write!(f, "cannot find table '{}' ({})", name, location)
+      }
+      Self::UnresolvedColumn { name, location } => {
+        write!(f, "cannot find column '{}' ({})", name, location)
+      }
+      Self::Duplicated {
+        kind,
+        name,
+        location,
+      } => write!(f, "duplicated {} '{}' ({})", kind, name, location),
+      Self::InvalidPrimaryKey { message, location } => {
+        write!(f, "invalid primary key: {} ({})", message, location)
+      }
+      Self::InvalidRef { message, location } => write!(f, "invalid ref: {} ({})", message, location),
+      Self::Config { message } => write!(f, "invalid config: {}", message),
+      Self::Io(err) => write!(f, "{}", err),
+    }
+  }
+}
+
+impl Error for CompilerError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      Self::Io(err) => Some(err),
+      _ => None,
+    }
+  }
+}
+
+impl From<io::Error> for CompilerError {
+  fn from(err: io::Error) -> Self {
+    Self::Io(err)
+  }
+}
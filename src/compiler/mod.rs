@@ -1,325 +1,754 @@
-use std::io::Result;
-
-use crate::generator::{Block, Codegen};
-use crate::{NAME, VERSION};
-
-use inflector::Inflector;
+use std::borrow::Cow;
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::{Path, PathBuf};
 
+use self::backend::Backend;
 use self::config::*;
-use self::traits::*;
+use self::err::*;
+use self::traits::{RAW_TYPE_NAMES, TYPE_NAME_ALIASES};
+use self::warn::*;
 
 use dbml_rs::*;
 
+pub mod backend;
 pub mod config;
+pub mod diff;
 pub mod err;
 pub mod traits;
 pub mod utils;
+pub mod warn;
+
+/// Generated file.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct OutputFile {
+  /// Path relative to the output directory. It is empty for the single-file output.
+  pub path: PathBuf,
+  pub code: String,
+}
+
+/// Generated files and the warnings raised while generating them.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Output {
+  pub files: Vec<OutputFile>,
+  pub warnings: Vec<CompilerWarning>,
+}
+
+impl Output {
+  /// Gets the generated code of the single-file output.
+  pub fn code(&self) -> Option<&str> {
+    match self.files.as_slice() {
+      [file] if file.path.as_os_str().is_empty() => Some(&file.code),
+      _ => None,
+    }
+  }
+
+  /// Writes the generated code into the output file, or into the output directory.
+  pub fn write(&self, out_path: impl AsRef<Path>) -> io::Result<()> {
+    let out_path = out_path.as_ref();
+
+    if let Some(code) = self.code() {
+      return fs::write(out_path, code);
+    }
+
+    fs::create_dir_all(out_path)?;
+
+    for file in self.files.iter() {
+      fs::write(out_path.join(&file.path), &file.code)?;
+    }
+
+    Ok(())
+  }
+
+  /// Compares the generated code with the files at the output path.
+  /// Returns the unified diff of every file that is stale or missing.
+  pub fn diff(&self, out_path: impl AsRef<Path>) -> io::Result<Vec<String>> {
+    let out_path = out_path.as_ref();
+
+    self
+      .files
+      .iter()
+      .filter_map(|file| {
+        let path = match self.code() {
+          Some(_) => out_path.to_path_buf(),
+          None => out_path.join(&file.path),
+        };
+
+        let current = match fs::read_to_string(&path) {
+          Ok(current) => current,
+          Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+          Err(err) => return Some(Err(err)),
+        };
+
+        let name = path.display().to_string();
+
+        utils::unified_diff(&current, &file.code, &name, &format!("{} (generated)", name)).map(Ok)
+      })
+      .collect()
+  }
+
+  /// Resolves the line and column numbers of the warnings against the given source.
+  pub fn with_source(self, input: &str) -> Self {
+    Self {
+      warnings: self
+        .warnings
+        .into_iter()
+        .map(|warning| warning.with_source(input))
+        .collect(),
+      ..self
+    }
+  }
+}
+
+/// Parses and analyzes the DBML content.
+/// Rejections from the parser and the analyzer, including the panicking ones, are reported as errors.
+pub fn parse(input: &str) -> Result<analyzer::SemanticSchemaBlock, CompilerError> {
+  let source = input;
+
+  // the parser only accepts 'PostgreSQL' as the database type, so the others are swapped before parsing
+  let database_type = utils::find_database_type(input)
+    .and_then(|range| parse_database_type(&input[range.clone()]).map(|ty| (range, ty)));
+
+  let input = match &database_type {
+    Some((range, _)) if &input[range.clone()] != "PostgreSQL" => Cow::Owned(format!(
+      "{}PostgreSQL{}",
+      &input[..range.start],
+      &input[range.end..]
+    )),
+    _ => Cow::Borrowed(input),
+  };
+
+  // the invalid constructs are rejected before analyzing, and the panics left to the analyzer are reported as errors
+  let result = panic::catch_unwind(|| parse_schema(&input, source));
+
+  match result {
+    Ok(Ok(mut sem_ast)) => {
+      if let (Some(project), Some((_, database_type))) = (&mut sem_ast.project, database_type) {
+        project.database_type = database_type;
+      }
+
+      Ok(sem_ast)
+    }
+    Ok(Err(err)) => Err(err.with_source(source)),
+    Err(payload) => {
+      let message = match payload.downcast::<String>() {
+        Ok(msg) => *msg,
+        Err(payload) => match payload.downcast::<&str>() {
+          Ok(msg) => msg.to_string(),
+          Err(_) => "the analyzer is aborted".to_string(),
+        },
+      };
+
+      Err(CompilerError::Parse {
+        message,
+        location: Box::new(Location::default()),
+      })
+    }
+  }
+}
+
+/// Parses the DBML content, with the constructs the analyzer rejects set aside while analyzing.
+/// The input may differ from the source by its database type, and the errors are located in the source.
+fn parse_schema(input: &str, source: &str) -> Result<analyzer::SemanticSchemaBlock, CompilerError> {
+  let mut schema = parser::parse(input).map_err(|err| syntax_error(&err, input, source))?;
+
+  locate_tables(&mut schema.tables, source);
+
+  // the analyzer requires a project block
+  let has_project = schema.project.is_some();
+  schema.project.get_or_insert_with(Default::default);
 
-pub fn compile(ast: analyzer::SemanticSchemaBlock, config: &Config) -> Result<String> {
-  let codegen = Codegen::new().line(format!("//! Generated by {NAME} {VERSION}"));
+  let raw_types = set_aside_types(&mut schema.tables, &schema.enums);
 
-  let codegen = if ast.enums.is_empty() {
-    codegen
-  } else {
-    codegen
-      .line_skip(1)
-      .line("use sea_orm::entity::prelude::*;")
+  check_schema(&schema)?;
+
+  let mut sem_ast = schema.analyze().map_err(|err| syntax_error(&err, input, source))?;
+
+  for ((table_idx, col_idx), r#type) in raw_types {
+    sem_ast.tables[table_idx].cols[col_idx].r#type = r#type;
+  }
+  if !has_project {
+    sem_ast.project = None;
+  }
+
+  Ok(sem_ast)
+}
+
+/// Converts an error of the parser into an error located in the source.
+fn syntax_error(err: &pest::error::Error<parser::Rule>, input: &str, source: &str) -> CompilerError {
+  let offset = match err.location {
+    pest::error::InputLocation::Pos(pos) => pos,
+    pest::error::InputLocation::Span((start, _)) => start,
   };
 
-  let codegen = gen_entity_modules(&ast, codegen, config);
-  let codegen = gen_enum_modules(&ast, codegen, config);
+  // the input only differs from the source by the database type, so the offsets after it are shifted alike
+  let prefix = input.bytes().zip(source.bytes()).take_while(|(a, b)| a == b).count();
+  let offset = match offset > prefix {
+    true => (offset + source.len()).saturating_sub(input.len()),
+    false => offset,
+  };
+  let (line, col) = utils::line_col(source, offset);
 
-  Ok(codegen.to_string())
+  CompilerError::Parse {
+    message: err.variant.message().to_string(),
+    location: Box::new(Location::line_col(line, col)),
+  }
 }
 
-fn gen_entity_modules(ast: &analyzer::SemanticSchemaBlock, codegen: Codegen, config: &Config) -> Codegen {
-  ast.tables.clone().iter().fold(codegen, |acc, table| {
-    let ast::table::TableBlock {
-      ident,
-      cols: fields,
-      indexes,
-      ..
-    } = table.clone();
-
-    let table_block = Block::new(2, Some("pub struct Model"));
-    let rel_block = Block::new(2, Some("pub enum Relation"));
-    let active_model_block = Block::new(2, Some("impl ActiveModelBehavior for ActiveModel"));
-    let mut rel_entity_blocks: Vec<_> = vec![];
-
-    // field listing
-    let table_block = fields.iter().cloned().fold(table_block, |acc, field| {
-      let mut out_fields = vec![];
-
-      if let Some(exp_type) = field.r#type.to_col_type() {
-        out_fields.push(format!(r#"column_type = "{}""#, exp_type))
+/// Sets the spans of the tables and their columns from their declarations in the source, as the parser leaves them empty.
+/// The spans of the column types are left empty, since the analyzer compares the types of the refs along with them.
+fn locate_tables(tables: &mut [ast::table::TableBlock], source: &str) {
+  let decls: Vec<_> = utils::scan_declarations(source)
+    .into_iter()
+    .filter(|decl| decl.keyword == "table")
+    .collect();
+
+  // the declarations are matched by their order, which is the one of the parser
+  if decls.len() != tables.len() {
+    return;
+  }
+
+  for (table, decl) in tables.iter_mut().zip(decls) {
+    if table.ident.name != decl.name || table.ident.schema != decl.schema {
+      continue;
+    }
+
+    table.span_range = decl.span;
+    table.ident.span_range = decl.name_span;
+
+    let is_matched = table.cols.len() == decl.columns.len()
+      && table.cols.iter().zip(decl.columns.iter()).all(|(col, decl)| col.name == decl.name);
+
+    if is_matched {
+      for (col, decl) in table.cols.iter_mut().zip(decl.columns) {
+        col.span_range = decl.span;
       }
-      if field.settings.is_pk {
-        out_fields.push(format!("primary_key"));
+    }
+  }
+}
 
-        if !field.settings.is_incremental {
-          out_fields.push(format!("auto_increment = false"))
+/// Checks the tables and the refs before they are analyzed, since the analyzer panics on the invalid ones.
+fn check_schema(schema: &ast::schema::SchemaBlock) -> Result<(), CompilerError> {
+  let find_table = |schema_name: &Option<String>, name: &str| {
+    schema.tables.iter().find(|table| {
+      (&table.ident.schema == schema_name && table.ident.name == name)
+        || (schema_name.is_none() && table.ident.alias.as_deref() == Some(name))
+    })
+  };
+
+  for (i, table) in schema.tables.iter().enumerate() {
+    let ident = &table.ident;
+
+    if schema.tables[..i]
+      .iter()
+      .any(|other| other.ident.schema == ident.schema && other.ident.name == ident.name)
+    {
+      return Err(CompilerError::Duplicated {
+        kind: "table",
+        name: ident.name.clone(),
+        location: Box::new(Location::table(table)),
+      });
+    }
+
+    let mut pk_count = 0;
+
+    for (j, col) in table.cols.iter().enumerate() {
+      if table.cols[..j].iter().any(|other| other.name == col.name) {
+        return Err(CompilerError::Duplicated {
+          kind: "column",
+          name: col.name.clone(),
+          location: Box::new(Location::column(ident, col)),
+        });
+      }
+
+      let is_bool = match &col.r#type.type_name {
+        ast::table::ColumnTypeName::Raw(raw) => raw.parse().ok() == Some(ast::table::ColumnTypeName::Bool),
+        type_name => *type_name == ast::table::ColumnTypeName::Bool,
+      };
+      let message = match &col.settings.default {
+        Some(ast::table::Value::Null) if !col.settings.is_nullable => {
+          Some("the default value of a non-nullable column cannot be null")
         }
-      } else if table.meta_indexer.pk_list.contains(&field.name) {
-        out_fields.push(format!("primary_key"));
+        Some(ast::table::Value::Bool(_)) if !is_bool => Some("a boolean default value requires a boolean column"),
+        _ => None,
+      };
+
+      if let Some(message) = message {
+        return Err(CompilerError::Parse {
+          message: message.to_string(),
+          location: Box::new(Location::column(ident, col)),
+        });
       }
-      if field.settings.is_nullable {
-        out_fields.push(format!("nullable"))
+
+      if !col.settings.is_pk {
+        continue;
       }
-      if table.meta_indexer.indexed_list.contains(&field.name) {
-        out_fields.push(format!("indexed"))
+
+      let message = match (col.settings.is_nullable, col.r#type.arrays.is_empty()) {
+        (true, _) => Some("a primary key cannot be nullable"),
+        (_, false) => Some("a primary key cannot be an array"),
+        _ => None,
+      };
+
+      if let Some(message) = message {
+        return Err(CompilerError::InvalidPrimaryKey {
+          message: message.to_string(),
+          location: Box::new(Location::column(ident, col)),
+        });
       }
-      if field.settings.is_unique || table.meta_indexer.unique_list.contains(&field.name) {
-        out_fields.push(format!("unique"))
+
+      pk_count += 1;
+    }
+
+    for def in table.indexes.iter().flat_map(|indexes| indexes.defs.iter()) {
+      for name in def.cols.iter().filter_map(|col| match col {
+        ast::indexes::IndexesColumnType::String(name) => Some(name),
+        _ => None,
+      }) {
+        if !table.cols.iter().any(|col| &col.name == name) {
+          return Err(CompilerError::UnresolvedColumn {
+            name: format!("{}.{}", ident.name, name),
+            location: Box::new(Location::table(table)),
+          });
+        }
       }
-      if let Some(default) = &field.settings.default {
-        let default_string = match default {
-          ast::table::Value::String(val) => format!(r#""{}""#, val),
-          ast::table::Value::Expr(val) => format!(r#""{}""#, val),
-          _ => default.to_string(),
-        };
 
-        match default {
-          ast::table::Value::Expr(_) => {
-            out_fields.push(format!(r#"default_expr = {}"#, default_string))
-          }
-          _ => out_fields.push(format!(r#"default_value = {}"#, default_string)),
-        };
+      if matches!(&def.settings, Some(settings) if settings.is_pk) {
+        pk_count += 1;
       }
+    }
+
+    if pk_count > 1 {
+      return Err(CompilerError::Duplicated {
+        kind: "primary key",
+        name: ident.name.clone(),
+        location: Box::new(Location::table(table)),
+      });
+    }
+  }
+
+  // the refs of the columns, then the ref blocks, with their location
+  let inline_refs = schema.tables.iter().flat_map(|table| {
+    table.cols.iter().flat_map(move |col| {
+      col.settings.refs.iter().map(move |ref_inline| {
+        let lhs = ast::refs::RefIdent {
+          schema: table.ident.schema.clone(),
+          table: table.ident.name.clone(),
+          compositions: vec![col.name.clone()],
+        };
 
-      let field_rust_type = field.r#type.to_rust_type();
-      let field_string = match field.settings.is_nullable {
-        true => format!("Option<{}>", field_rust_type),
-        false => field_rust_type,
-      };
+        (lhs, &ref_inline.rhs, Location::column(&table.ident, col))
+      })
+    })
+  });
+  let block_refs = schema.refs.iter().map(|ref_block| {
+    let lhs_table = find_table(&ref_block.lhs.schema, &ref_block.lhs.table);
+    let lhs_col = lhs_table.and_then(|table| {
+      let name = ref_block.lhs.compositions.first()?;
 
-      acc
-        .line_cond(
-          !out_fields.is_empty(),
-          format!("#[sea_orm({})]", out_fields.join(", ")),
-        )
-        .line(format!("pub {}: {},", field.name, field_string))
+      table.cols.iter().find(|col| &col.name == name)
     });
 
-    // relation listing
-    let (rto_vec, rby_vec, rself_vec) = ast.get_table_refs(&ident);
-
-    let rel_block = rself_vec.into_iter().fold(rel_block, |acc, rto| {
-      let from_field_pascal = rto.lhs.compositions.get(0).unwrap().to_pascal_case();
-      let to_field_pascal = rto.rhs.compositions.get(0).unwrap().to_pascal_case();
-
-      let derive = {
-        let mut attrs = vec![
-          format!(r#"belongs_to = "Entity""#),
-          format!(r#"from = "Column::{}""#, from_field_pascal),
-          format!(r#"to = "Column::{}""#, to_field_pascal),
-        ];
-
-        if let Some(settings) = rto.settings {
-          if let Some(action) = settings.on_delete {
-            attrs.push(format!(
-              r#"on_delete = "{}""#,
-              action.to_string().to_pascal_case()
-            ))
-          }
-          if let Some(action) = settings.on_update {
-            attrs.push(format!(
-              r#"on_update = "{}""#,
-              action.to_string().to_pascal_case()
-            ))
-          }
-        }
+    let location = match (lhs_table, lhs_col) {
+      (Some(table), Some(col)) => Location::column(&table.ident, col),
+      (Some(table), None) => Location::table(table),
+      _ => Location::default(),
+    };
+
+    (ref_block.lhs.clone(), &ref_block.rhs, location)
+  });
+
+  let mut lhs_cols = vec![];
+
+  for (lhs, rhs, location) in inline_refs.chain(block_refs) {
+    let mut cols = vec![];
+
+    for ident in [&lhs, rhs] {
+      let table = find_table(&ident.schema, &ident.table).ok_or_else(|| CompilerError::UnresolvedTable {
+        name: ident.table.clone(),
+        location: Box::new(location.clone()),
+      })?;
+
+      let side_cols = ident
+        .compositions
+        .iter()
+        .map(|name| {
+          table
+            .cols
+            .iter()
+            .find(|col| &col.name == name)
+            .ok_or_else(|| CompilerError::UnresolvedColumn {
+              name: format!("{}.{}", ident.table, name),
+              location: Box::new(location.clone()),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+      cols.push((&table.ident, side_cols));
+    }
+
+    let (lhs_table, lhs_side) = &cols[0];
+    let (_, rhs_side) = &cols[1];
+
+    if lhs_side.len() != rhs_side.len() {
+      return Err(CompilerError::InvalidRef {
+        message: "both sides must have the same number of columns".to_string(),
+        location: Box::new(location),
+      });
+    }
+
+    // the type names are compared as the analyzer resolves them
+    let type_name = |col: &ast::table::TableColumn| match &col.r#type.type_name {
+      ast::table::ColumnTypeName::Raw(raw) => raw.parse().unwrap_or_else(|_| col.r#type.type_name.clone()),
+      type_name => type_name.clone(),
+    };
+    let mismatched = lhs_side.iter().zip(rhs_side.iter()).find(|(lhs, rhs)| {
+      type_name(lhs) != type_name(rhs)
+        || lhs.r#type.args != rhs.r#type.args
+        || lhs.r#type.arrays != rhs.r#type.arrays
+    });
 
-        format!(r#"#[sea_orm({})]"#, attrs.join(", "))
-      };
+    if let Some((lhs, rhs)) = mismatched {
+      return Err(CompilerError::InvalidRef {
+        message: format!("the types of the columns '{}' and '{}' are different", lhs.name, rhs.name),
+        location: Box::new(location),
+      });
+    }
 
-      rel_entity_blocks.push(Block::new(2, Some("pub struct SelfReferencingLink")));
+    // a column refers to a single column, whether in a ref block or in its settings
+    let key = (lhs_table.schema.clone(), lhs_table.name.clone(), lhs.compositions.clone());
 
-      rel_entity_blocks.push(
-        Block::new(2, Some("impl Linked for SelfReferencingLink"))
-          .line("type FromEntity = Entity;")
-          .line("type ToEntity = Entity;")
-          .line_skip(1)
-          .block(
-            Block::new(3, Some("fn link(&self) -> Vec<RelationDef>"))
-              .line("vec![Relation::SelfReferencing.def()]"),
-          ),
-      );
+    if lhs_cols.contains(&key) {
+      return Err(CompilerError::Duplicated {
+        kind: "ref",
+        name: format!("{}.{}", lhs_table.name, lhs.compositions.join(", ")),
+        location: Box::new(location),
+      });
+    }
 
-      acc.line(derive).line("SelfReferencing,")
-    });
+    lhs_cols.push(key);
+  }
 
-    let rel_block = rto_vec.into_iter().fold(rel_block, |acc, rto| {
-      let from_field_pascal = rto.lhs.compositions.get(0).unwrap().to_pascal_case();
-      let to_field_pascal = rto.rhs.compositions.get(0).unwrap().to_pascal_case();
-      let name_pascal = rto.rhs.table.to_pascal_case();
-      let name_snake = rto.rhs.table.to_snake_case();
-
-      let derive = match rto.rel {
-        ast::refs::Relation::One2One | ast::refs::Relation::Many2One => {
-          let mut attrs = vec![
-            format!(r#"belongs_to = "super::{}::Entity""#, name_snake),
-            format!(r#"from = "Column::{}""#, from_field_pascal),
-            format!(
-              r#"to = "super::{}::Column::{}""#,
-              name_snake, to_field_pascal
-            ),
-          ];
-
-          if let Some(settings) = rto.settings {
-            if let Some(action) = settings.on_delete {
-              attrs.push(format!(
-                r#"on_delete = "{}""#,
-                action.to_string().to_pascal_case()
-              ))
-            }
-            if let Some(action) = settings.on_update {
-              attrs.push(format!(
-                r#"on_update = "{}""#,
-                action.to_string().to_pascal_case()
-              ))
-            }
-          }
-
-          format!(r#"#[sea_orm({})]"#, attrs.join(", "))
-        }
-        _ => panic!("unsupported_rel"),
-      };
+  Ok(())
+}
 
-      rel_entity_blocks.push(
-        Block::new(
-          2,
-          Some(format!(
-            "impl Related<super::{}::Entity> for Entity",
-            name_snake
-          )),
-        )
-        .block(
-          Block::new(3, Some("fn to() -> RelationDef"))
-            .line(format!("Relation::{}.def()", name_pascal)),
-        ),
-      );
+/// Normalizes the raw type names of the columns, and replaces the types that the analyzer does not accept
+/// (unknown type names, or arguments such as the display width of `int(11)`) with a placeholder.
+/// The names that are neither types nor enums are set aside as well, to be reported along with their column.
+/// Returns the types to put back after analyzing, by table and column index.
+fn set_aside_types(
+  tables: &mut [ast::table::TableBlock],
+  enums: &[ast::enums::EnumBlock],
+) -> Vec<((usize, usize), ast::table::ColumnType)> {
+  use ast::table::{ColumnTypeName, Value};
+
+  // the schema of the enums is checked by the analyzer
+  let is_enum = |raw: &str| {
+    let name = raw.rsplit('.').next().unwrap_or(raw);
+
+    enums.iter().any(|r#enum| r#enum.ident.name == name)
+  };
 
-      acc.line(derive).line(format!("{},", name_pascal))
-    });
+  let mut out = vec![];
 
-    let rel_block = rby_vec.into_iter().fold(rel_block, |acc, rby| {
-      let name_pascal = rby.lhs.table.to_pascal_case();
-      let name_snake = rby.lhs.table.to_snake_case();
+  for (table_idx, table) in tables.iter_mut().enumerate() {
+    for (col_idx, col) in table.cols.iter_mut().enumerate() {
+      let (raw, name) = match &col.r#type.type_name {
+        ColumnTypeName::Raw(raw) => (raw, raw.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()),
+        _ => continue,
+      };
+      let name = match TYPE_NAME_ALIASES.iter().find(|(alias, _)| *alias == name) {
+        Some((_, name)) => name.to_string(),
+        None => name,
+      };
 
-      let derive = match rby.rel {
-        ast::refs::Relation::One2One => {
-          format!(r#"#[sea_orm(has_one = "super::{}::Entity")]"#, name_snake)
-        }
-        ast::refs::Relation::Many2One => {
-          format!(r#"#[sea_orm(has_many = "super::{}::Entity")]"#, name_snake)
+      let type_name = name.parse::<ColumnTypeName>().ok();
+      let is_accepted = match (&type_name, col.r#type.args.len()) {
+        (Some(_), 0) => true,
+        (Some(ColumnTypeName::Char | ColumnTypeName::VarChar), 1) => true,
+        (Some(ColumnTypeName::Decimal), 2) => true,
+        (Some(_), _) => false,
+        // the enums are resolved by the analyzer
+        (None, _) => is_enum(raw) && !RAW_TYPE_NAMES.contains(&name.as_str()),
+      };
+
+      if is_accepted {
+        if type_name.is_some() {
+          col.r#type.type_name = ColumnTypeName::Raw(name);
         }
-        _ => panic!("unsupported_rel"),
+
+        continue;
+      }
+
+      // the analyzer checks boolean defaults against the type
+      let placeholder = match col.settings.default {
+        Some(Value::Bool(_)) => "bool",
+        _ => "text",
       };
 
-      rel_entity_blocks.push(
-        Block::new(
-          2,
-          Some(format!(
-            "impl Related<super::{}::Entity> for Entity",
-            name_snake
-          )),
-        )
-        .block(
-          Block::new(3, Some("fn to() -> RelationDef"))
-            .line(format!("Relation::{}.def()", name_pascal)),
-        ),
-      );
+      let r#type = ast::table::ColumnType {
+        span_range: col.r#type.span_range.clone(),
+        type_name: type_name.unwrap_or(ColumnTypeName::Raw(name)),
+        args: std::mem::take(&mut col.r#type.args),
+        arrays: col.r#type.arrays.clone(),
+      };
+      col.r#type.type_name = ColumnTypeName::Raw(placeholder.to_string());
 
-      acc.line(derive).line(format!("{},", name_pascal))
-    });
+      out.push(((table_idx, col_idx), r#type));
+    }
+  }
 
-    // active model listing
-    /* let active_model_item: Vec<_> = fields
-    .iter()
-    .cloned()
-    .filter_map(|field| {
-      let timestamp = "Utc::now().naive_utc()";
-      if field.settings.note == Some(format!("@updated_at")) {
-        Some(format!("{}: Set({}),", field.name, timestamp))
-      }
-      else if field.settings.default == Some(ast::table::Value::Expr(format!("now()"))) {
-        Some(format!("{}: Set(self.{}.take().or_else(|| Some({}))),", field.name, field.name, timestamp))
-      }
-      else {
-        None
-      }
-    })
-    .chain([
-      format!("..self")
-    ])
-    .collect(); */
-
-    // construct mod block
-    let mod_block = Block::new(1, Some(format!("pub mod {}", &ident.name.to_snake_case())))
-      .line("use sea_orm::entity::prelude::*;")
-      .line_skip(1)
-      .line(format!(
-        "#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]"
-      ))
-      .line(format!(
-        r#"#[sea_orm(table_name = "{}", schema_name = "{}")]"#,
-        &ident.name,
-        &ident.schema.unwrap_or_else(|| DEFAULT_SCHEMA.into())
+  out
+}
+
+/// Gets the database type of a `database_type` value, compared case-insensitively.
+fn parse_database_type(value: &str) -> Option<ast::project::DatabaseType> {
+  use ast::project::DatabaseType;
+
+  match value.to_lowercase().as_str() {
+    "postgresql" | "postgres" => Some(DatabaseType::PostgreSQL),
+    "mysql" => Some(DatabaseType::MySQL),
+    "mariadb" => Some(DatabaseType::MariaDB),
+    "sqlite" => Some(DatabaseType::SQLite),
+    "mssql" | "sql server" | "sqlserver" => Some(DatabaseType::MSSQL),
+    "oracle" => Some(DatabaseType::Oracle),
+    "mongodb" => Some(DatabaseType::MongoDB),
+    _ => None,
+  }
+}
+
+/// Compiles the semantic schema with the backend of the configuration (SeaORM by default).
+pub fn compile(
+  mut ast: analyzer::SemanticSchemaBlock,
+  config: &Config,
+) -> Result<Output, CompilerError> {
+  normalize_refs(&mut ast);
+  filter_tables(&mut ast, config);
+
+  let mut warnings = vec![];
+
+  // the target declared by the project is used when the configuration does not set one
+  let database_type = ast
+    .project
+    .as_ref()
+    .map(|project| project.database_type.clone())
+    .unwrap_or_default();
+  let target = config.resolve_target(&database_type);
+
+  if let Some(project) = &ast.project {
+    if database_type != ast::project::DatabaseType::Undef
+      && Target::from_database_type(&database_type).as_ref() != Some(&target)
+    {
+      let message = match config.target {
+        Some(_) => format!(
+          "database type '{:?}' of the project does not match the target '{}'",
+          database_type, target
+        ),
+        None => format!(
+          "database type '{:?}' of the project has no target, '{}' is used",
+          database_type, target
+        ),
+      };
+
+      warnings.push(CompilerWarning::new(
+        WarningCode::TargetMismatch,
+        message,
+        Location::block("project", &project.name),
       ))
-      .block(table_block)
-      .line_skip(1)
-      .line("#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]")
-      .block(rel_block)
-      .block_vec(rel_entity_blocks)
-      .line_skip(1)
-      .line("impl ActiveModelBehavior for ActiveModel {}");
-
-    acc.line_skip(1).block(mod_block)
+    }
+  }
+
+  let config = &Config {
+    target: Some(target),
+    ..config.clone()
+  };
+
+  warnings.extend(collect_warnings(&ast));
+
+  let output = match &config.backend {
+    Some(backend) => backend.generate(&ast, config)?,
+    None => backend::SeaOrm.generate(&ast, config)?,
+  };
+
+  warnings.extend(output.warnings);
+
+  Ok(Output {
+    files: output.files,
+    warnings,
   })
 }
 
-fn gen_enum_modules(ast: &analyzer::SemanticSchemaBlock, codegen: Codegen, config: &Config) -> Codegen {
-  let rs_type = match config.enum_type {
-    EnumType::Integer => format!("i32"),
-    EnumType::String(_) => format!("String")
+/// Compiles the changes from the old version of the semantic schema to the new one into an incremental migration,
+/// with the backend of the configuration (sea-orm-migration by default).
+/// The target is resolved from the new version.
+pub fn compile_diff(
+  mut old: analyzer::SemanticSchemaBlock,
+  mut new: analyzer::SemanticSchemaBlock,
+  config: &Config,
+) -> Result<Output, CompilerError> {
+  for ast in [&mut old, &mut new] {
+    normalize_refs(ast);
+    filter_tables(ast, config);
+  }
+
+  let database_type = new
+    .project
+    .as_ref()
+    .map(|project| project.database_type.clone())
+    .unwrap_or_default();
+
+  let config = &Config {
+    target: Some(config.resolve_target(&database_type)),
+    ..config.clone()
   };
 
-  let db_type = match config.enum_type {
-    EnumType::Integer => format!("Integer"),
-    EnumType::String(_) if config.is_native_enum => format!("Enum"),
-    EnumType::String(size) => format!("String({:?})", size)
-  };
-  
-  ast.enums.clone().into_iter().fold(codegen, |acc, r#enum| {
-    let ast::enums::EnumBlock {
-      ident: ast::enums::EnumIdent { name, schema },
-      values,
-    } = r#enum;
-
-    let enum_block = Block::new(1, Some(format!("pub enum {}", name.to_pascal_case())));
-
-    let enum_block = values.into_iter().enumerate().fold(enum_block, |acc, (i, value)| {
-      let value_name = value.value;
-      let value = match config.enum_type {
-        EnumType::Integer => format!(r#"num_value = {}"#, i),
-        EnumType::String(_) => format!(r#"string_value = "{}""#, value_name)
-      };
+  let diff = diff::diff(&old, &new);
 
-      acc
-        .line(format!(r#"#[sea_orm({})]"#, value))
-        .line(format!("{},", value_name.to_pascal_case()))
-    });
+  match &config.backend {
+    Some(backend) => backend.generate_diff(&diff, &old, &new, config),
+    None => backend::SeaOrmMigration.generate_diff(&diff, &old, &new, config),
+  }
+}
 
-    acc
-      .line_skip(1)
-      .line("#[derive(Clone, Debug, PartialEq, EnumIter, DeriveActiveEnum)]")
-      .line(format!(
-        r#"#[sea_orm(rs_type = "{}", db_type = "{}", enum_name = "{}", schema_name = "{}")]"#,
-        rs_type,
-        db_type,
-        name,
-        schema.unwrap_or_else(|| DEFAULT_SCHEMA.into())
+/// Collects the constructs that are accepted but dropped from the generated code.
+fn collect_warnings(ast: &analyzer::SemanticSchemaBlock) -> Vec<CompilerWarning> {
+  let mut warnings = vec![];
+
+  for table in ast.tables.iter() {
+    let name = &table.ident.name;
+
+    if let Some(settings) = &table.settings {
+      if settings
+        .iter()
+        .any(|(key, _)| key.eq_ignore_ascii_case("headercolor"))
+      {
+        warnings.push(CompilerWarning::new(
+          WarningCode::HeaderColor,
+          format!("headercolor of table '{}' is ignored", name),
+          Location::table(table),
+        ))
+      }
+    }
+  }
+
+  for group in ast.table_groups.iter() {
+    warnings.push(CompilerWarning::new(
+      WarningCode::TableGroup,
+      format!("table group '{}' is ignored", group.name),
+      Location::block("tablegroup", &group.name),
+    ))
+  }
+
+  for ref_block in ast.refs.iter() {
+    if ref_block.lhs.compositions.len() > 1 {
+      let lhs = ast.indexer.refer_ref_alias(&ref_block.lhs);
+      let rhs = ast.indexer.refer_ref_alias(&ref_block.rhs);
+
+      warnings.push(CompilerWarning::new(
+        WarningCode::CompositeRef,
+        format!(
+          "only the first column of the composite ref ({}) -> ({}) is used",
+          lhs.compositions.join(", "),
+          rhs.compositions.join(", ")
+        ),
+        ref_location(ast, &ref_block.lhs),
       ))
-      .block(enum_block)
-  })
+    }
+  }
+
+  warnings
 }
+
+/// Rewrites one-to-many refs (`<`) into many-to-one refs (`>`) so that the lhs is always the referencing side.
+fn normalize_refs(ast: &mut analyzer::SemanticSchemaBlock) {
+  for ref_block in ast.refs.iter_mut() {
+    if ref_block.rel == ast::refs::Relation::One2Many {
+      std::mem::swap(&mut ref_block.lhs, &mut ref_block.rhs);
+      ref_block.rel = ast::refs::Relation::Many2One;
+    }
+  }
+}
+
+/// Drops the tables left out by the include and exclude filters, along with the refs touching them.
+fn filter_tables(ast: &mut analyzer::SemanticSchemaBlock, config: &Config) {
+  if config.include.is_empty() && config.exclude.is_empty() {
+    return;
+  }
+
+  ast
+    .tables
+    .retain(|table| config.is_table_included(&table.ident.name));
+
+  let refs = std::mem::take(&mut ast.refs);
+
+  ast.refs = refs
+    .into_iter()
+    .filter(|ref_block| {
+      [&ref_block.lhs, &ref_block.rhs]
+        .into_iter()
+        .all(|ident| config.is_table_included(&ast.indexer.refer_ref_alias(ident).table))
+    })
+    .collect();
+}
+
+/// Resolves the alias of a ref identifier and checks that the table and its columns exist.
+pub fn resolve_ref_ident(
+  ast: &analyzer::SemanticSchemaBlock,
+  ident: &ast::refs::RefIdent,
+  location: &Location,
+) -> Result<ast::refs::RefIdent, CompilerError> {
+  let ident = ast.indexer.refer_ref_alias(ident);
+
+  let table = ast
+    .tables
+    .iter()
+    .find(|table| table.ident.schema == ident.schema && table.ident.name == ident.table)
+    .ok_or_else(|| CompilerError::UnresolvedTable {
+      name: ident.table.clone(),
+      location: Box::new(location.clone()),
+    })?;
+
+  let unresolved_col = ident
+    .compositions
+    .iter()
+    .find(|name| !table.cols.iter().any(|col| &col.name == *name));
+
+  match (unresolved_col, ident.compositions.first()) {
+    (Some(name), _) => Err(CompilerError::UnresolvedColumn {
+      name: format!("{}.{}", ident.table, name),
+      location: Box::new(location.clone()),
+    }),
+    (None, None) => Err(CompilerError::UnresolvedColumn {
+      name: ident.table.clone(),
+      location: Box::new(location.clone()),
+    }),
+    (None, Some(_)) => Ok(ident),
+  }
+}
+
+/// Gets the location of the referencing side of a ref.
+pub fn ref_location(ast: &analyzer::SemanticSchemaBlock, lhs: &ast::refs::RefIdent) -> Location {
+  let lhs = ast.indexer.refer_ref_alias(lhs);
+
+  let table = ast
+    .tables
+    .iter()
+    .find(|table| table.ident.schema == lhs.schema && table.ident.name == lhs.table);
+  let col = table.and_then(|table| {
+    let name = lhs.compositions.first()?;
+
+    table.cols.iter().find(|col| &col.name == name)
+  });
+
+  match (table, col) {
+    (Some(table), Some(col)) => Location::column(&table.ident, col),
+    (Some(table), None) => Location::table(table),
+    (None, _) => Location::table_ident(&ast::table::TableIdent {
+      schema: lhs.schema,
+      name: lhs.table,
+      ..Default::default()
+    }),
+  }
+}
+
@@ -2,86 +2,730 @@ use inflector::Inflector;
 
 use dbml_rs::ast::*;
 
+use super::config::*;
+use super::err::*;
+
+/// Type names beyond the ones known by the DBML analyzer.
+/// The columns of these types are kept as `ColumnTypeName::Raw` with the lowercase name.
+pub const RAW_TYPE_NAMES: &[&str] = &[
+  "tinyint",
+  "mediumint",
+  "datetime",
+  "float",
+  "interval",
+  "blob",
+  "tinyblob",
+  "mediumblob",
+  "longblob",
+  "binary",
+  "varbinary",
+  "tinyint unsigned",
+  "smallint unsigned",
+  "mediumint unsigned",
+  "int unsigned",
+  "integer unsigned",
+  "bigint unsigned",
+];
+
+/// Type names spelled differently by other databases, along with the name known by the DBML analyzer.
+pub const TYPE_NAME_ALIASES: &[(&str, &str)] = &[
+  ("string", "varchar"),
+  ("nvarchar", "varchar"),
+  ("nchar", "char"),
+  ("double", "double precision"),
+];
+
 pub trait ToRustType {
-  fn to_rust_type(&self) -> String;
+  fn to_rust_type(&self, config: &Config) -> Result<String, CompilerError>;
 }
 
 pub trait ToColType {
-  fn to_col_type(&self) -> Option<String>;
+  /// Gets the `column_type` of a SeaORM entity, or `None` for the enums whose type is given by their active enum.
+  fn to_col_type(&self, config: &Config) -> Result<Option<String>, CompilerError>;
 }
 
-impl ToRustType for table::ColumnType {
-  fn to_rust_type(&self) -> String {
-    let str_type = match &self.type_name {
-      table::ColumnTypeName::Enum(name) => format!("super::{}", name.to_pascal_case()),
-      table::ColumnTypeName::Char => format!("String"),
-      table::ColumnTypeName::VarChar => format!("String"),
-      table::ColumnTypeName::SmallInt => format!("i16"),
-      table::ColumnTypeName::Integer => format!("i32"),
-      table::ColumnTypeName::BigInt => format!("i64"),
-      table::ColumnTypeName::Real => format!("f32"),
-      table::ColumnTypeName::DoublePrecision => format!("f64"),
-      table::ColumnTypeName::Bool => format!("bool"),
-      table::ColumnTypeName::ByteArray => format!("Vec<u8>"),
-      table::ColumnTypeName::Date => format!("Date"),
-      table::ColumnTypeName::Text => format!("String"),
-      table::ColumnTypeName::Time => format!("Time"),
-      table::ColumnTypeName::Timestamp => format!("DateTime"),
-      table::ColumnTypeName::Timestamptz => format!("DateTimeWithTimeZone"),
-      table::ColumnTypeName::Uuid => format!("Uuid"),
-      table::ColumnTypeName::Json => format!("Json"),
-      table::ColumnTypeName::Decimal => format!("Decimal"),
-      _ => panic!("cannot_format_type_to_seaorm_type"),
+pub trait ToColumnDef {
+  fn to_column_def(&self, config: &Config) -> Result<String, CompilerError>;
+}
+
+pub trait ToSqlType {
+  fn to_sql_type(&self, config: &Config) -> Result<String, CompilerError>;
+}
+
+pub trait ToDieselType {
+  fn to_diesel_type(&self, config: &Config) -> Result<String, CompilerError>;
+}
+
+/// Qualifies the type aliases of the SeaORM prelude (e.g. `DateTime`) with the paths of their crates,
+/// for the generated code that does not depend on SeaORM.
+pub fn qualify_rust_type(rust_type: &str) -> String {
+  if let Some(inner) = rust_type.strip_prefix("Vec<").and_then(|rest| rest.strip_suffix('>')) {
+    return format!("Vec<{}>", qualify_rust_type(inner));
+  }
+
+  match rust_type {
+    "Date" => "chrono::NaiveDate",
+    "Time" => "chrono::NaiveTime",
+    "DateTime" => "chrono::NaiveDateTime",
+    "DateTimeWithTimeZone" => "chrono::DateTime<chrono::FixedOffset>",
+    "Uuid" => "uuid::Uuid",
+    "Json" => "serde_json::Value",
+    "DateTimeUtc" => "chrono::DateTime<chrono::Utc>",
+    "TimeDate" => "time::Date",
+    "TimeTime" => "time::Time",
+    "TimeDateTime" => "time::PrimitiveDateTime",
+    "TimeDateTimeWithTimeZone" => "time::OffsetDateTime",
+    "Decimal" => "rust_decimal::Decimal",
+    "BigDecimal" => "bigdecimal::BigDecimal",
+    "IpNetwork" => "ipnetwork::IpNetwork",
+    _ => rust_type,
+  }
+  .to_string()
+}
+
+/// Gets the Rust type of a column given by the `@rust_type: <type>` annotation of its note, if any.
+/// The annotated type replaces the whole type of the field, apart from the `Option` of a nullable column.
+pub fn annotated_rust_type(col: &table::TableColumn) -> Option<String> {
+  col.settings.note.as_ref()?.lines().find_map(|line| {
+    let (_, rust_type) = line.split_once("@rust_type:")?;
+
+    match rust_type.trim() {
+      "" => None,
+      rust_type => Some(rust_type.to_string()),
+    }
+  })
+}
+
+/// Gets the SeaORM prelude alias of a date and time type in the configured crate.
+fn date_time_rust_type(type_name: &table::ColumnTypeName, config: &Config) -> String {
+  match (config.date_time_crate, type_name) {
+    (DateTimeCrate::Time, table::ColumnTypeName::Date) => "TimeDate",
+    (DateTimeCrate::Time, table::ColumnTypeName::Time) => "TimeTime",
+    (DateTimeCrate::Time, table::ColumnTypeName::Timestamptz) => "TimeDateTimeWithTimeZone",
+    (DateTimeCrate::Time, _) => "TimeDateTime",
+    (_, table::ColumnTypeName::Date) => "Date",
+    (_, table::ColumnTypeName::Time) => "Time",
+    (DateTimeCrate::ChronoUtc, table::ColumnTypeName::Timestamptz) => "DateTimeUtc",
+    (_, table::ColumnTypeName::Timestamptz) => "DateTimeWithTimeZone",
+    (_, _) => "DateTime",
+  }
+  .to_string()
+}
+
+/// Gets the SeaORM prelude alias of the decimal type in the configured crate.
+fn decimal_rust_type(config: &Config) -> String {
+  match config.decimal_crate {
+    DecimalCrate::RustDecimal => "Decimal",
+    DecimalCrate::BigDecimal => "BigDecimal",
+  }
+  .to_string()
+}
+
+/// Spells the SeaORM `ColumnType::String` of the given length, as parsed by the derive macros of the targeted release.
+pub fn string_col_type(len: Option<String>, config: &Config) -> String {
+  match (config.sea_orm_version, len) {
+    (SeaOrmVersion::V1, Some(len)) => format!("String(StringLen::N({}))", len),
+    (SeaOrmVersion::V1, None) => "String(StringLen::None)".to_string(),
+    (_, Some(len)) => format!("String(Some({}))", len),
+    (_, None) => "String(None)".to_string(),
+  }
+}
+
+/// Gets the name of a column type as written in DBML, for the error messages.
+pub fn type_name_str(type_name: &table::ColumnTypeName) -> String {
+  match type_name {
+    table::ColumnTypeName::Raw(name) | table::ColumnTypeName::Enum(name) => name.clone(),
+    table::ColumnTypeName::DoublePrecision => "double precision".to_string(),
+    table::ColumnTypeName::ByteArray => "bytea".to_string(),
+    type_name => format!("{:?}", type_name).to_lowercase(),
+  }
+}
+
+/// Finds the type override of a column type, preferring the one named along with the arguments of the type.
+/// The names are compared as the DBML analyzer reads them, so that `int` names the `integer` columns.
+pub fn find_type_override<'a>(r#type: &table::ColumnType, config: &'a Config) -> Option<&'a TypeOverride> {
+  let args: Vec<_> = r#type.args.iter().map(|arg| arg.to_string()).collect();
+
+  let matches = |key: &str, with_args: bool| {
+    let (name, key_args) = match key.split_once('(') {
+      Some((name, rest)) => (name, Some(rest.trim_end().strip_suffix(')').unwrap_or(rest))),
+      None => (key, None),
+    };
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let name = match TYPE_NAME_ALIASES.iter().find(|(alias, _)| *alias == name) {
+      Some((_, name)) => name.to_string(),
+      None => name,
+    };
+
+    let is_same_name = match name.parse::<table::ColumnTypeName>() {
+      Ok(type_name) => type_name == r#type.type_name,
+      Err(_) => name == type_name_str(&r#type.type_name),
+    };
+    let is_same_args = match key_args {
+      Some(key_args) => with_args && key_args.split(',').map(str::trim).eq(args.iter().map(String::as_str)),
+      None => !with_args,
     };
 
-    self
-      .arrays
+    is_same_name && is_same_args
+  };
+
+  [true, false].iter().find_map(|with_args| {
+    config
+      .type_overrides
       .iter()
-      .fold(str_type, |acc, arr| format!("Vec<{}>", acc))
+      .find(|(key, _)| matches(key, *with_args))
+      .map(|(_, type_override)| type_override)
+  })
+}
+
+/// Checks whether the type only exists in Postgres.
+fn is_postgres_only(type_name: &table::ColumnTypeName) -> bool {
+  match type_name {
+    table::ColumnTypeName::Inet
+    | table::ColumnTypeName::Cidr
+    | table::ColumnTypeName::MacAddr
+    | table::ColumnTypeName::MacAddr8
+    | table::ColumnTypeName::Money
+    | table::ColumnTypeName::Varbit
+    | table::ColumnTypeName::Xml
+    | table::ColumnTypeName::Timetz => true,
+    table::ColumnTypeName::Raw(name) => name == "interval",
+    _ => false,
+  }
+}
+
+/// Rejects the Postgres types on the other targets.
+fn check_target(type_name: &table::ColumnTypeName, target: &Target) -> Result<(), CompilerError> {
+  match *target != Target::Postgres && is_postgres_only(type_name) {
+    true => Err(CompilerError::UnsupportedFeature {
+      feature: format!("'{}' column type", type_name_str(type_name)),
+      target: target.clone(),
+      location: Box::new(Location::default()),
+    }),
+    false => Ok(()),
+  }
+}
+
+/// Rejects the array columns on the targets without array types.
+fn check_array(r#type: &table::ColumnType, target: &Target) -> Result<(), CompilerError> {
+  match !r#type.arrays.is_empty() && !target.has_array() {
+    true => Err(CompilerError::UnsupportedFeature {
+      feature: "array column type".to_string(),
+      target: target.clone(),
+      location: Box::new(Location::default()),
+    }),
+    false => Ok(()),
+  }
+}
+
+/// Gets the Rust type of an array column from the type of its elements.
+/// The drivers only decode one-dimensional arrays, so the others are rejected by the backends beforehand.
+pub fn array_rust_type(elem_type: String, arrays: &[Option<usize>]) -> String {
+  match arrays.is_empty() {
+    true => elem_type,
+    false => format!("Vec<{}>", elem_type),
+  }
+}
+
+/// Gets the path of the reference-counted pointer held by `ColumnType::Array` in the targeted release.
+fn rc_path(config: &Config) -> &'static str {
+  match config.sea_orm_version {
+    SeaOrmVersion::V0_11 => "sea_orm::sea_query::SeaRc",
+    SeaOrmVersion::V0_12 | SeaOrmVersion::V1 => "sea_orm::sea_query::RcOrArc",
+  }
+}
+
+fn unsupported_type(type_name: &table::ColumnTypeName) -> CompilerError {
+  CompilerError::UnsupportedType {
+    type_name: type_name_str(type_name),
+    location: Box::new(Location::default()),
+  }
+}
+
+impl ToRustType for table::ColumnType {
+  fn to_rust_type(&self, config: &Config) -> Result<String, CompilerError> {
+    let target = config.target.clone().unwrap_or(Target::Postgres);
+    let enum_mod = match config.output_mode {
+      OutputMode::File => "super",
+      OutputMode::Directory => "super::sea_orm_active_enums",
+    };
+
+    let str_type = match find_type_override(self, config) {
+      Some(type_override) => type_override.rust_type.clone(),
+      None => {
+        check_target(&self.type_name, &target)?;
+
+        match &self.type_name {
+          table::ColumnTypeName::Enum(name) => format!("{}::{}", enum_mod, name.to_pascal_case()),
+          table::ColumnTypeName::Char => "String".to_string(),
+          table::ColumnTypeName::VarChar => "String".to_string(),
+          table::ColumnTypeName::SmallInt | table::ColumnTypeName::SmallSerial => "i16".to_string(),
+          table::ColumnTypeName::Integer | table::ColumnTypeName::Serial => "i32".to_string(),
+          table::ColumnTypeName::BigInt | table::ColumnTypeName::BigSerial => "i64".to_string(),
+          table::ColumnTypeName::Real => "f32".to_string(),
+          table::ColumnTypeName::DoublePrecision => "f64".to_string(),
+          table::ColumnTypeName::Bool => "bool".to_string(),
+          table::ColumnTypeName::ByteArray => "Vec<u8>".to_string(),
+          table::ColumnTypeName::Date
+          | table::ColumnTypeName::Time
+          | table::ColumnTypeName::Timestamp
+          | table::ColumnTypeName::Timestamptz => date_time_rust_type(&self.type_name, config),
+          table::ColumnTypeName::Text => "String".to_string(),
+          table::ColumnTypeName::Uuid => "Uuid".to_string(),
+          table::ColumnTypeName::Json | table::ColumnTypeName::Jsonb => "Json".to_string(),
+          table::ColumnTypeName::Decimal | table::ColumnTypeName::Money => decimal_rust_type(config),
+          // a single bit is a flag
+          table::ColumnTypeName::Bit if matches!(self.args.as_slice(), [] | [table::Value::Integer(1)]) => {
+            "bool".to_string()
+          }
+          table::ColumnTypeName::Bit | table::ColumnTypeName::Varbit => "Vec<u8>".to_string(),
+          table::ColumnTypeName::Inet | table::ColumnTypeName::Cidr if config.sea_orm_version == SeaOrmVersion::V1 => {
+            "IpNetwork".to_string()
+          }
+          // the types without a Rust counterpart in SeaORM are read as text, as by sea-orm-cli
+          table::ColumnTypeName::Inet
+          | table::ColumnTypeName::Cidr
+          | table::ColumnTypeName::MacAddr
+          | table::ColumnTypeName::MacAddr8
+          | table::ColumnTypeName::Xml
+          | table::ColumnTypeName::Timetz => "String".to_string(),
+          table::ColumnTypeName::Raw(name) => match name.as_str() {
+            "tinyint" => "i8".to_string(),
+            "mediumint" => "i32".to_string(),
+            "datetime" => date_time_rust_type(&table::ColumnTypeName::Timestamp, config),
+            // `float` is single precision in MySQL only
+            "float" if target == Target::MySql => "f32".to_string(),
+            "float" => "f64".to_string(),
+            "interval" => "String".to_string(),
+            "blob" | "tinyblob" | "mediumblob" | "longblob" | "binary" | "varbinary" => "Vec<u8>".to_string(),
+            "tinyint unsigned" if target == Target::MySql => "u8".to_string(),
+            "smallint unsigned" if target == Target::MySql => "u16".to_string(),
+            "mediumint unsigned" | "int unsigned" | "integer unsigned" if target == Target::MySql => {
+              "u32".to_string()
+            }
+            "bigint unsigned" if target == Target::MySql => "u64".to_string(),
+            _ => return Err(unsupported_type(&self.type_name)),
+          },
+          type_name => return Err(unsupported_type(type_name)),
+        }
+      }
+    };
+
+    check_array(self, &target)?;
+
+    Ok(array_rust_type(str_type, &self.arrays))
   }
 }
 
 impl ToColType for table::ColumnType {
-  fn to_col_type(&self) -> Option<String> {
+  fn to_col_type(&self, config: &Config) -> Result<Option<String>, CompilerError> {
+    let target = config.target.clone().unwrap_or(Target::Postgres);
+    let is_v1 = config.sea_orm_version == SeaOrmVersion::V1;
+
     let str_arg_vec: Vec<_> = self.args.iter().map(|arg| arg.to_string()).collect();
 
     let str_arg = match str_arg_vec.len() {
-      0 => format!("None"),
+      0 => "None".to_string(),
       1 => format!("Some({})", str_arg_vec.join(", ")),
       _ => format!("Some(({}))", str_arg_vec.join(", ")),
     };
+    let len_arg = str_arg_vec.first().cloned();
+
+    let type_override = find_type_override(self, config);
+
+    check_array(self, &target)?;
 
-    let str_type = match self.type_name {
-      table::ColumnTypeName::Char => Some(format!("Char({})", str_arg)),
-      table::ColumnTypeName::VarChar => Some(format!("String({})", str_arg)),
-      table::ColumnTypeName::SmallInt => Some(format!("SmallInteger")),
-      table::ColumnTypeName::Integer => Some(format!("Integer")),
-      table::ColumnTypeName::BigInt => Some(format!("BigInteger")),
-      table::ColumnTypeName::Real => Some(format!("Float")),
-      table::ColumnTypeName::DoublePrecision => Some(format!("Double")),
-      table::ColumnTypeName::Bool => Some(format!("Boolean")),
-      table::ColumnTypeName::ByteArray => Some(format!("Binary")),
-      table::ColumnTypeName::Date => Some(format!("Date")),
-      table::ColumnTypeName::Text => Some(format!("Text")),
-      table::ColumnTypeName::Time => Some(format!("Time")),
-      table::ColumnTypeName::Timestamp => Some(format!("DateTime")),
-      table::ColumnTypeName::Timestamptz => Some(format!("TimestampWithTimeZone")),
-      table::ColumnTypeName::Uuid => Some(format!("Uuid")),
-      table::ColumnTypeName::Json => Some(format!("Json")),
-      table::ColumnTypeName::Decimal => Some(format!("Decimal({})", str_arg)),
-      _ => None,
+    let str_type = match type_override.and_then(|type_override| type_override.column_type.clone()) {
+      Some(col_type) => col_type,
+      None => match &self.type_name {
+        table::ColumnTypeName::Enum(_) => return Ok(None),
+        // SQLite stores the values by type affinity, where the length of the text is not enforced
+        table::ColumnTypeName::Char | table::ColumnTypeName::VarChar if target == Target::Sqlite => {
+          "Text".to_string()
+        }
+        table::ColumnTypeName::SmallInt | table::ColumnTypeName::SmallSerial if target == Target::Sqlite => {
+          "Integer".to_string()
+        }
+        table::ColumnTypeName::Raw(name) if target == Target::Sqlite && (name == "tinyint" || name == "mediumint") => {
+          "Integer".to_string()
+        }
+        table::ColumnTypeName::Char => format!("Char({})", str_arg),
+        table::ColumnTypeName::VarChar => string_col_type(len_arg, config),
+        table::ColumnTypeName::SmallInt | table::ColumnTypeName::SmallSerial => "SmallInteger".to_string(),
+        table::ColumnTypeName::Integer | table::ColumnTypeName::Serial => "Integer".to_string(),
+        table::ColumnTypeName::BigInt | table::ColumnTypeName::BigSerial => "BigInteger".to_string(),
+        table::ColumnTypeName::Real => "Float".to_string(),
+        table::ColumnTypeName::DoublePrecision => "Double".to_string(),
+        table::ColumnTypeName::Bool => "Boolean".to_string(),
+        table::ColumnTypeName::ByteArray if is_v1 => "VarBinary(StringLen::None)".to_string(),
+        table::ColumnTypeName::ByteArray => "Binary(BlobSize::Blob(None))".to_string(),
+        table::ColumnTypeName::Date => "Date".to_string(),
+        table::ColumnTypeName::Text => "Text".to_string(),
+        table::ColumnTypeName::Time => "Time".to_string(),
+        table::ColumnTypeName::Timestamp => "DateTime".to_string(),
+        table::ColumnTypeName::Timestamptz => "TimestampWithTimeZone".to_string(),
+        table::ColumnTypeName::Uuid => "Uuid".to_string(),
+        table::ColumnTypeName::Json => "Json".to_string(),
+        table::ColumnTypeName::Jsonb => "JsonBinary".to_string(),
+        // a single precision is the scale-less decimal
+        table::ColumnTypeName::Decimal => match str_arg_vec.as_slice() {
+          [precision] => format!("Decimal(Some(({}, 0)))", precision),
+          _ => format!("Decimal({})", str_arg),
+        },
+        table::ColumnTypeName::Money => "Money(None)".to_string(),
+        table::ColumnTypeName::Bit => format!("Bit({})", str_arg),
+        table::ColumnTypeName::Varbit => match len_arg {
+          Some(len) => format!("VarBit({})", len),
+          None => r#"custom("varbit")"#.to_string(),
+        },
+        table::ColumnTypeName::Inet => "Inet".to_string(),
+        table::ColumnTypeName::Cidr => "Cidr".to_string(),
+        table::ColumnTypeName::MacAddr => "MacAddr".to_string(),
+        table::ColumnTypeName::MacAddr8 => r#"custom("macaddr8")"#.to_string(),
+        table::ColumnTypeName::Xml => r#"custom("xml")"#.to_string(),
+        table::ColumnTypeName::Timetz => r#"custom("timetz")"#.to_string(),
+        table::ColumnTypeName::Raw(name) => match name.as_str() {
+          "tinyint" => "TinyInteger".to_string(),
+          "mediumint" => "Integer".to_string(),
+          "datetime" => "DateTime".to_string(),
+          "float" if target == Target::MySql => "Float".to_string(),
+          "float" => "Double".to_string(),
+          "interval" => "Interval(None, None)".to_string(),
+          // SeaORM 1.x has no blob sizes apart from the MySQL types
+          "blob" if is_v1 => "Blob".to_string(),
+          "tinyblob" | "mediumblob" | "longblob" if is_v1 => format!(r#"custom("{}")"#, name),
+          "binary" if is_v1 => format!("Binary({})", len_arg.unwrap_or_else(|| "1".to_string())),
+          "varbinary" if is_v1 => format!("VarBinary(StringLen::N({}))", len_arg.unwrap_or_else(|| "255".to_string())),
+          "blob" => "Binary(BlobSize::Blob(None))".to_string(),
+          "tinyblob" => "Binary(BlobSize::Tiny)".to_string(),
+          "mediumblob" => "Binary(BlobSize::Medium)".to_string(),
+          "longblob" => "Binary(BlobSize::Long)".to_string(),
+          "binary" => format!("Binary(BlobSize::Blob(Some({})))", len_arg.unwrap_or_else(|| "1".to_string())),
+          "varbinary" => format!("VarBinary({})", len_arg.unwrap_or_else(|| "255".to_string())),
+          "tinyint unsigned" => "TinyUnsigned".to_string(),
+          "smallint unsigned" => "SmallUnsigned".to_string(),
+          "mediumint unsigned" | "int unsigned" | "integer unsigned" => "Unsigned".to_string(),
+          "bigint unsigned" => "BigUnsigned".to_string(),
+          // the column type of an unknown name is left to the derive macro
+          _ if type_override.is_some() => return Ok(None),
+          _ => return Err(unsupported_type(&self.type_name)),
+        },
+        type_name => return Err(unsupported_type(type_name)),
+      },
     };
 
-    match str_type {
-      Some(s) => {
-        let r = self.arrays.iter().fold(s, |acc, arr| {
-          // FIXME: not sure
-          format!("Array<Arc<{}>>", acc)
-        });
+    // the sizes of the dimensions are not enforced by Postgres
+    match self.arrays.is_empty() {
+      true => Ok(Some(str_type)),
+      false => Ok(Some(format!(
+        "Array({}::new(ColumnType::{}))",
+        rc_path(config),
+        str_type
+      ))),
+    }
+  }
+}
 
-        Some(r)
+impl ToDieselType for table::ColumnType {
+  fn to_diesel_type(&self, config: &Config) -> Result<String, CompilerError> {
+    let target = config.target.clone().unwrap_or(Target::Postgres);
+
+    let str_type = match &self.type_name {
+      table::ColumnTypeName::Enum(name) => match config.enum_type {
+        EnumType::String(_) if config.is_native_enum && target.has_native_enum() => {
+          name.to_pascal_case()
+        }
+        EnumType::String(Some(_)) => "Varchar".to_string(),
+        EnumType::String(None) => "Text".to_string(),
+        EnumType::Integer => "Integer".to_string(),
+      },
+      table::ColumnTypeName::Char if target == Target::Postgres => "Bpchar".to_string(),
+      table::ColumnTypeName::Char => "Varchar".to_string(),
+      table::ColumnTypeName::VarChar => "Varchar".to_string(),
+      table::ColumnTypeName::SmallInt | table::ColumnTypeName::SmallSerial => "SmallInt".to_string(),
+      table::ColumnTypeName::Integer | table::ColumnTypeName::Serial => "Integer".to_string(),
+      table::ColumnTypeName::BigInt | table::ColumnTypeName::BigSerial => "BigInt".to_string(),
+      table::ColumnTypeName::Real => "Float".to_string(),
+      table::ColumnTypeName::DoublePrecision => "Double".to_string(),
+      table::ColumnTypeName::Bool => "Bool".to_string(),
+      table::ColumnTypeName::ByteArray => "Binary".to_string(),
+      table::ColumnTypeName::Date => "Date".to_string(),
+      table::ColumnTypeName::Text => "Text".to_string(),
+      table::ColumnTypeName::Time => "Time".to_string(),
+      table::ColumnTypeName::Timestamp => "Timestamp".to_string(),
+      table::ColumnTypeName::Timestamptz => "Timestamptz".to_string(),
+      table::ColumnTypeName::Uuid => "Uuid".to_string(),
+      table::ColumnTypeName::Json => "Json".to_string(),
+      table::ColumnTypeName::Jsonb if target == Target::Postgres => "Jsonb".to_string(),
+      table::ColumnTypeName::Jsonb => "Json".to_string(),
+      table::ColumnTypeName::Decimal => "Numeric".to_string(),
+      table::ColumnTypeName::Money => "Money".to_string(),
+      table::ColumnTypeName::Inet => "Inet".to_string(),
+      table::ColumnTypeName::Cidr => "Cidr".to_string(),
+      table::ColumnTypeName::MacAddr => "MacAddr".to_string(),
+      table::ColumnTypeName::MacAddr8 => "MacAddr8".to_string(),
+      table::ColumnTypeName::Raw(name) => match name.as_str() {
+        "tinyint" => "TinyInt".to_string(),
+        "mediumint" => "Integer".to_string(),
+        "datetime" if target == Target::MySql => "Datetime".to_string(),
+        "datetime" => "Timestamp".to_string(),
+        "float" if target == Target::MySql => "Float".to_string(),
+        "float" => "Double".to_string(),
+        "interval" => "Interval".to_string(),
+        "blob" | "tinyblob" | "mediumblob" | "longblob" | "binary" | "varbinary" => "Binary".to_string(),
+        "tinyint unsigned" => "Unsigned<TinyInt>".to_string(),
+        "smallint unsigned" => "Unsigned<SmallInt>".to_string(),
+        "mediumint unsigned" | "int unsigned" | "integer unsigned" => "Unsigned<Integer>".to_string(),
+        "bigint unsigned" => "Unsigned<BigInt>".to_string(),
+        _ => return Err(unsupported_type(&self.type_name)),
+      },
+      // Diesel has no SQL types for the bit strings, `xml` and `timetz`
+      type_name => return Err(unsupported_type(type_name)),
+    };
+
+    match self.arrays.is_empty() {
+      true => Ok(str_type),
+      false => Ok(format!("Array<{}>", str_type)),
+    }
+  }
+}
+
+impl ToColumnDef for table::ColumnType {
+  fn to_column_def(&self, config: &Config) -> Result<String, CompilerError> {
+    let target = config.target.clone().unwrap_or(Target::Postgres);
+    let is_v1 = config.sea_orm_version == SeaOrmVersion::V1;
+
+    check_target(&self.type_name, &target)?;
+
+    check_array(self, &target)?;
+
+    if !self.arrays.is_empty() {
+      // the dimensions and sizes are only kept by the SQL type
+      if self.arrays.len() > 1 || self.arrays.iter().any(Option::is_some) {
+        return Ok(format!(r#"custom(Alias::new("{}"))"#, self.to_sql_type(config)?));
       }
-      None => None,
+
+      let elem_type = table::ColumnType {
+        arrays: vec![],
+        ..self.clone()
+      };
+      let col_type = match elem_type.to_col_type(config)? {
+        Some(col_type) => col_type,
+        None if find_type_override(self, config).is_some() => {
+          format!(r#"Custom(SeaRc::new(Alias::new("{}")))"#, type_name_str(&self.type_name))
+        }
+        None => return Err(unsupported_type(&self.type_name)),
+      };
+
+      return Ok(format!("array(ColumnType::{})", col_type));
     }
+
+    let args: Vec<_> = self.args.iter().map(|arg| arg.to_string()).collect();
+    let custom = |name: &str| format!(r#"custom(Alias::new("{}"))"#, name);
+    let with_args = |name: &str| match args.is_empty() {
+      true => name.to_string(),
+      false => format!("{}({})", name, args.join(", ")),
+    };
+
+    let def = match &self.type_name {
+      table::ColumnTypeName::Enum(_) => match config.enum_type {
+        EnumType::String(Some(len)) => format!("string_len({})", len),
+        EnumType::String(None) => "string()".to_string(),
+        EnumType::Integer => "integer()".to_string(),
+      },
+      table::ColumnTypeName::Char => match args.as_slice() {
+        [len] => format!("char_len({})", len),
+        _ => "char()".to_string(),
+      },
+      table::ColumnTypeName::VarChar => match args.as_slice() {
+        [len] => format!("string_len({})", len),
+        _ => "string()".to_string(),
+      },
+      table::ColumnTypeName::SmallInt | table::ColumnTypeName::SmallSerial => "small_integer()".to_string(),
+      table::ColumnTypeName::Integer | table::ColumnTypeName::Serial => "integer()".to_string(),
+      table::ColumnTypeName::BigInt | table::ColumnTypeName::BigSerial => "big_integer()".to_string(),
+      table::ColumnTypeName::Real => "float()".to_string(),
+      table::ColumnTypeName::DoublePrecision => "double()".to_string(),
+      table::ColumnTypeName::Bool => "boolean()".to_string(),
+      // the blobs are `bytea` in Postgres
+      table::ColumnTypeName::ByteArray if is_v1 => "blob()".to_string(),
+      table::ColumnTypeName::ByteArray => "binary()".to_string(),
+      table::ColumnTypeName::Date => "date()".to_string(),
+      table::ColumnTypeName::Text => "text()".to_string(),
+      table::ColumnTypeName::Time => "time()".to_string(),
+      table::ColumnTypeName::Timestamp => "timestamp()".to_string(),
+      table::ColumnTypeName::Timestamptz => "timestamp_with_time_zone()".to_string(),
+      table::ColumnTypeName::Uuid => "uuid()".to_string(),
+      table::ColumnTypeName::Json => "json()".to_string(),
+      table::ColumnTypeName::Jsonb => "json_binary()".to_string(),
+      table::ColumnTypeName::Decimal => match args.as_slice() {
+        [precision, scale] => format!("decimal_len({}, {})", precision, scale),
+        [precision] => format!("decimal_len({}, 0)", precision),
+        _ => "decimal()".to_string(),
+      },
+      table::ColumnTypeName::Money => "money()".to_string(),
+      table::ColumnTypeName::Bit => match args.as_slice() {
+        [len] => format!("bit(Some({}))", len),
+        _ => "bit(None)".to_string(),
+      },
+      table::ColumnTypeName::Varbit => match args.as_slice() {
+        [len] => format!("varbit({})", len),
+        _ => custom("varbit"),
+      },
+      table::ColumnTypeName::Inet => "inet()".to_string(),
+      table::ColumnTypeName::Cidr => "cidr()".to_string(),
+      table::ColumnTypeName::MacAddr => "mac_address()".to_string(),
+      table::ColumnTypeName::MacAddr8 => custom("macaddr8"),
+      table::ColumnTypeName::Xml => custom("xml"),
+      table::ColumnTypeName::Timetz => custom("timetz"),
+      table::ColumnTypeName::Raw(name) => match name.as_str() {
+        "tinyint" => "tiny_integer()".to_string(),
+        "mediumint" => "integer()".to_string(),
+        "datetime" => "date_time()".to_string(),
+        "float" if target == Target::MySql => "float()".to_string(),
+        "float" => "double()".to_string(),
+        "interval" => "interval(None, None)".to_string(),
+        "blob" if is_v1 => "blob()".to_string(),
+        "tinyblob" | "mediumblob" | "longblob" if is_v1 => custom(name),
+        "blob" => "blob(BlobSize::Blob(None))".to_string(),
+        "tinyblob" => "blob(BlobSize::Tiny)".to_string(),
+        "mediumblob" => "blob(BlobSize::Medium)".to_string(),
+        "longblob" => "blob(BlobSize::Long)".to_string(),
+        "binary" => format!("binary_len({})", args.first().map_or("1", String::as_str)),
+        "varbinary" => format!("var_binary({})", args.first().map_or("255", String::as_str)),
+        "tinyint unsigned" if target == Target::MySql => "tiny_unsigned()".to_string(),
+        "smallint unsigned" if target == Target::MySql => "small_unsigned()".to_string(),
+        "mediumint unsigned" | "int unsigned" | "integer unsigned" if target == Target::MySql => {
+          "unsigned()".to_string()
+        }
+        "bigint unsigned" if target == Target::MySql => "big_unsigned()".to_string(),
+        _ if find_type_override(self, config).is_some() => custom(&with_args(name)),
+        _ => return Err(unsupported_type(&self.type_name)),
+      },
+      type_name => return Err(unsupported_type(type_name)),
+    };
+
+    Ok(def)
+  }
+}
+
+impl ToSqlType for table::ColumnType {
+  fn to_sql_type(&self, config: &Config) -> Result<String, CompilerError> {
+    let target = config.target.clone().unwrap_or(Target::Postgres);
+
+    check_target(&self.type_name, &target)?;
+    check_array(self, &target)?;
+
+    let args: Vec<_> = self.args.iter().map(|arg| arg.to_string()).collect();
+    let with_args = |name: &str| match args.is_empty() {
+      true => name.to_string(),
+      false => format!("{}({})", name, args.join(", ")),
+    };
+
+    let str_type = match (&target, &self.type_name) {
+      (_, table::ColumnTypeName::Enum(_)) => match config.enum_type {
+        EnumType::String(Some(len)) => format!("varchar({})", len),
+        EnumType::String(None) => "text".to_string(),
+        EnumType::Integer => "integer".to_string(),
+      },
+      // SQLite only keeps the type affinity of the columns
+      (Target::Sqlite, type_name) => match type_name {
+        table::ColumnTypeName::Char
+        | table::ColumnTypeName::VarChar
+        | table::ColumnTypeName::Text
+        | table::ColumnTypeName::Uuid
+        | table::ColumnTypeName::Json
+        | table::ColumnTypeName::Jsonb => "text".to_string(),
+        table::ColumnTypeName::SmallInt
+        | table::ColumnTypeName::Integer
+        | table::ColumnTypeName::BigInt
+        | table::ColumnTypeName::SmallSerial
+        | table::ColumnTypeName::Serial
+        | table::ColumnTypeName::BigSerial
+        | table::ColumnTypeName::Bit => "integer".to_string(),
+        table::ColumnTypeName::Real | table::ColumnTypeName::DoublePrecision => "real".to_string(),
+        table::ColumnTypeName::Decimal => "numeric".to_string(),
+        table::ColumnTypeName::Bool => "boolean".to_string(),
+        table::ColumnTypeName::ByteArray => "blob".to_string(),
+        table::ColumnTypeName::Date => "date".to_string(),
+        table::ColumnTypeName::Time => "time".to_string(),
+        table::ColumnTypeName::Timestamp | table::ColumnTypeName::Timestamptz => "timestamp".to_string(),
+        table::ColumnTypeName::Raw(name) => match name.as_str() {
+          "tinyint" | "mediumint" => "integer".to_string(),
+          "float" => "real".to_string(),
+          "datetime" => "datetime".to_string(),
+          "blob" | "tinyblob" | "mediumblob" | "longblob" | "binary" | "varbinary" => "blob".to_string(),
+          _ if find_type_override(self, config).is_some() => with_args(name),
+          _ => return Err(unsupported_type(type_name)),
+        },
+        type_name => return Err(unsupported_type(type_name)),
+      },
+      (Target::MySql, table::ColumnTypeName::VarChar) if args.is_empty() => "varchar(255)".to_string(),
+      (Target::MySql, table::ColumnTypeName::Integer | table::ColumnTypeName::Serial) => "int".to_string(),
+      (Target::MySql, table::ColumnTypeName::SmallSerial) => "smallint".to_string(),
+      (Target::MySql, table::ColumnTypeName::BigSerial) => "bigint".to_string(),
+      (Target::MySql, table::ColumnTypeName::DoublePrecision) => "double".to_string(),
+      (Target::MySql, table::ColumnTypeName::Real) => "float".to_string(),
+      (Target::MySql, table::ColumnTypeName::ByteArray) => "blob".to_string(),
+      (Target::MySql, table::ColumnTypeName::Timestamptz) => with_args("timestamp"),
+      (Target::MySql, table::ColumnTypeName::Uuid) => "binary(16)".to_string(),
+      (Target::MySql, table::ColumnTypeName::Jsonb) => "json".to_string(),
+      (Target::MySql, table::ColumnTypeName::Raw(name)) => match name.as_str() {
+        "tinyint" | "mediumint" | "tinyint unsigned" | "smallint unsigned" | "mediumint unsigned"
+        | "int unsigned" | "bigint unsigned" | "blob" | "tinyblob" | "mediumblob" | "longblob" => name.clone(),
+        "integer unsigned" => "int unsigned".to_string(),
+        "datetime" | "float" | "binary" => with_args(name),
+        "varbinary" if args.is_empty() => "varbinary(255)".to_string(),
+        "varbinary" => with_args(name),
+        _ if find_type_override(self, config).is_some() => with_args(name),
+        _ => return Err(unsupported_type(&self.type_name)),
+      },
+      (Target::Postgres, table::ColumnTypeName::Raw(name)) => match name.as_str() {
+        "tinyint" => "smallint".to_string(),
+        "mediumint" => "integer".to_string(),
+        "datetime" => with_args("timestamp"),
+        "float" if args.is_empty() => "double precision".to_string(),
+        "float" | "interval" => with_args(name),
+        "blob" | "tinyblob" | "mediumblob" | "longblob" | "binary" | "varbinary" => "bytea".to_string(),
+        _ if find_type_override(self, config).is_some() => with_args(name),
+        _ => return Err(unsupported_type(&self.type_name)),
+      },
+      (Target::Postgres, table::ColumnTypeName::ByteArray) => "bytea".to_string(),
+      (Target::Postgres, table::ColumnTypeName::Real) => "real".to_string(),
+      (Target::Postgres, table::ColumnTypeName::DoublePrecision) => "double precision".to_string(),
+      (Target::Postgres, table::ColumnTypeName::Timestamptz) => with_args("timestamptz"),
+      (Target::Postgres, table::ColumnTypeName::Uuid) => "uuid".to_string(),
+      (Target::Postgres, table::ColumnTypeName::Jsonb) => "jsonb".to_string(),
+      (Target::Postgres, table::ColumnTypeName::SmallSerial) => "smallserial".to_string(),
+      (Target::Postgres, table::ColumnTypeName::Serial) => "serial".to_string(),
+      (Target::Postgres, table::ColumnTypeName::BigSerial) => "bigserial".to_string(),
+      (Target::Postgres, table::ColumnTypeName::Inet) => "inet".to_string(),
+      (Target::Postgres, table::ColumnTypeName::Cidr) => "cidr".to_string(),
+      (Target::Postgres, table::ColumnTypeName::MacAddr) => "macaddr".to_string(),
+      (Target::Postgres, table::ColumnTypeName::MacAddr8) => "macaddr8".to_string(),
+      (Target::Postgres, table::ColumnTypeName::Money) => "money".to_string(),
+      (Target::Postgres, table::ColumnTypeName::Xml) => "xml".to_string(),
+      (Target::Postgres, table::ColumnTypeName::Timetz) => with_args("timetz"),
+      (Target::Postgres, table::ColumnTypeName::Varbit) => with_args("varbit"),
+      (_, table::ColumnTypeName::Char) => with_args("char"),
+      (_, table::ColumnTypeName::VarChar) => with_args("varchar"),
+      (_, table::ColumnTypeName::SmallInt) => "smallint".to_string(),
+      (_, table::ColumnTypeName::Integer) => "integer".to_string(),
+      (_, table::ColumnTypeName::BigInt) => "bigint".to_string(),
+      (_, table::ColumnTypeName::Bool) => "boolean".to_string(),
+      (_, table::ColumnTypeName::Bit) => with_args("bit"),
+      (_, table::ColumnTypeName::Date) => "date".to_string(),
+      (_, table::ColumnTypeName::Text) => "text".to_string(),
+      (_, table::ColumnTypeName::Time) => with_args("time"),
+      (_, table::ColumnTypeName::Timestamp) => with_args("timestamp"),
+      (_, table::ColumnTypeName::Json) => "json".to_string(),
+      (_, table::ColumnTypeName::Decimal) => with_args("decimal"),
+      (_, type_name) => return Err(unsupported_type(type_name)),
+    };
+
+    Ok(self.arrays.iter().fold(str_type, |acc, size| match size {
+      Some(size) => format!("{}[{}]", acc, size),
+      None => format!("{}[]", acc),
+    }))
   }
 }
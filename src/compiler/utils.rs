@@ -1 +1,490 @@
+use std::ops::Range;
 
+/// Top-level block (e.g. `Table` or `Enum`) declared in the DBML source.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Declaration {
+  /// Keyword of the block, in lowercase.
+  pub keyword: String,
+  pub schema: Option<String>,
+  pub name: String,
+  /// Byte range from the keyword to the closing brace.
+  pub span: Range<usize>,
+  /// Byte range of the (possibly schema-qualified) name.
+  pub name_span: Range<usize>,
+  /// Columns declared in the block, in order. Only tables have columns.
+  pub columns: Vec<ColumnDeclaration>,
+}
+
+/// Column declared in a table block.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ColumnDeclaration {
+  pub name: String,
+  /// Byte range from the name to the end of the settings.
+  pub span: Range<usize>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum TokenKind {
+  Word,
+  /// Quoted identifier, whose text is without the quotes.
+  Quoted,
+  /// String, expression or hex color.
+  Literal,
+  Newline,
+  Punct(char),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Token<'a> {
+  kind: TokenKind,
+  text: &'a str,
+  span: Range<usize>,
+}
+
+/// Finds the top-level blocks declared in the DBML source, skipping the comments and the strings.
+/// The blocks are listed in the order of the source, as they are by the parser.
+pub fn scan_declarations(input: &str) -> Vec<Declaration> {
+  let tokens = tokenize(input);
+  let mut out = vec![];
+  let mut i = 0;
+
+  while i < tokens.len() {
+    let keyword = match &tokens[i] {
+      Token {
+        kind: TokenKind::Word,
+        text,
+        ..
+      } => text.to_lowercase(),
+      _ => {
+        i += 1;
+        continue;
+      }
+    };
+
+    let start = tokens[i].span.start;
+    i += 1;
+
+    // the refs have no name to look them up by
+    if !matches!(keyword.as_str(), "table" | "enum" | "tablegroup" | "project") {
+      i = skip_statement(&tokens, i);
+      continue;
+    }
+
+    let (schema, name, name_span) = match qualified_name(&tokens, i) {
+      Some((schema, name, name_span, next)) => {
+        i = next;
+        (schema, name, name_span)
+      }
+      None => {
+        i = skip_statement(&tokens, i);
+        continue;
+      }
+    };
+
+    // the alias and the settings are between the name and the body
+    let body_start = match find_token(&tokens, i, TokenKind::Punct('{')) {
+      Some(body_start) => body_start,
+      None => break,
+    };
+    let body_end = matching_brace(&tokens, body_start);
+    let columns = match keyword.as_str() {
+      "table" => scan_columns(&tokens[body_start + 1..body_end.min(tokens.len())]),
+      _ => vec![],
+    };
+    let end = tokens.get(body_end).map_or(input.len(), |token| token.span.end);
+
+    out.push(Declaration {
+      keyword,
+      schema,
+      name,
+      span: start..end,
+      name_span,
+      columns,
+    });
+
+    i = body_end + 1;
+  }
+
+  out
+}
+
+/// Gets the line and column numbers (1-based) of a byte offset in the source.
+pub fn line_col(input: &str, offset: usize) -> (usize, usize) {
+  let before = &input[..offset.min(input.len())];
+  let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+  (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+}
+
+/// Splits the source into tokens, without the whitespaces and the comments.
+fn tokenize(input: &str) -> Vec<Token<'_>> {
+  let bytes = input.as_bytes();
+  let mut out = vec![];
+  let mut i = 0;
+
+  // finds the end of a delimited text, after the closing delimiter
+  let close = |from: usize, delim: &str, escapable: bool| {
+    let mut j = from;
+
+    while j < input.len() {
+      if escapable && bytes[j] == b'\\' {
+        j += 2;
+      } else if bytes[j..].starts_with(delim.as_bytes()) {
+        return j + delim.len();
+      } else {
+        j += 1;
+      }
+    }
+
+    input.len()
+  };
+
+  while i < input.len() {
+    let rest = &input[i..];
+    let c = rest.chars().next().unwrap_or_default();
+
+    let (kind, end) = if c == '\n' {
+      (Some(TokenKind::Newline), i + 1)
+    } else if c.is_whitespace() {
+      (None, i + c.len_utf8())
+    } else if rest.starts_with("//") {
+      (None, rest.find('\n').map_or(input.len(), |j| i + j))
+    } else if rest.starts_with("/*") {
+      (None, close(i + 2, "*/", false))
+    } else if rest.starts_with("'''") {
+      (Some(TokenKind::Literal), close(i + 3, "'''", true))
+    } else if c == '\'' || c == '`' {
+      (Some(TokenKind::Literal), close(i + 1, &c.to_string(), c == '\''))
+    } else if c == '"' {
+      (Some(TokenKind::Quoted), close(i + 1, "\"", true))
+    } else if c == '#' {
+      (Some(TokenKind::Literal), i + 1 + rest[1..].find(|c: char| !c.is_alphanumeric()).unwrap_or(rest.len() - 1))
+    } else if c.is_alphanumeric() || c == '_' {
+      (Some(TokenKind::Word), i + rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len()))
+    } else {
+      (Some(TokenKind::Punct(c)), i + c.len_utf8())
+    };
+
+    if let Some(kind) = kind {
+      let text = match kind {
+        TokenKind::Quoted => input[i + 1..end].strip_suffix('"').unwrap_or(&input[i + 1..end]),
+        _ => &input[i..end],
+      };
+
+      out.push(Token {
+        kind,
+        text,
+        span: i..end,
+      });
+    }
+
+    i = end;
+  }
+
+  out
+}
+
+/// Reads a (possibly schema-qualified) name. Returns the schema, the name, its span and the next token index.
+fn qualified_name(tokens: &[Token], i: usize) -> Option<(Option<String>, String, Range<usize>, usize)> {
+  let is_ident = |token: Option<&Token>| matches!(token, Some(token) if matches!(token.kind, TokenKind::Word | TokenKind::Quoted));
+
+  if !is_ident(tokens.get(i)) {
+    return None;
+  }
+
+  match tokens.get(i + 1) {
+    Some(dot) if dot.kind == TokenKind::Punct('.') && is_ident(tokens.get(i + 2)) => Some((
+      Some(tokens[i].text.to_string()),
+      tokens[i + 2].text.to_string(),
+      tokens[i].span.start..tokens[i + 2].span.end,
+      i + 3,
+    )),
+    _ => Some((None, tokens[i].text.to_string(), tokens[i].span.clone(), i + 1)),
+  }
+}
+
+/// Finds the next token of the kind outside of the brackets.
+fn find_token(tokens: &[Token], from: usize, kind: TokenKind) -> Option<usize> {
+  let mut depth = 0usize;
+
+  for (i, token) in tokens.iter().enumerate().skip(from) {
+    match token.kind {
+      _ if token.kind == kind && depth == 0 => return Some(i),
+      TokenKind::Punct('[') => depth += 1,
+      TokenKind::Punct(']') => depth = depth.saturating_sub(1),
+      _ => (),
+    }
+  }
+
+  None
+}
+
+/// Gets the index of the brace closing the one at the given index (or the end of the tokens).
+fn matching_brace(tokens: &[Token], open: usize) -> usize {
+  let mut depth = 0usize;
+
+  for (i, token) in tokens.iter().enumerate().skip(open) {
+    match token.kind {
+      TokenKind::Punct('{') => depth += 1,
+      TokenKind::Punct('}') if depth == 1 => return i,
+      TokenKind::Punct('}') => depth -= 1,
+      _ => (),
+    }
+  }
+
+  tokens.len()
+}
+
+/// Skips a statement without a name (e.g. `Ref: a.id > b.id` or `Ref { ... }`).
+fn skip_statement(tokens: &[Token], from: usize) -> usize {
+  let mut i = from;
+
+  while let Some(token) = tokens.get(i) {
+    match token.kind {
+      TokenKind::Newline => return i + 1,
+      TokenKind::Punct('{') => return matching_brace(tokens, i) + 1,
+      _ => i += 1,
+    }
+  }
+
+  i
+}
+
+/// Finds the columns declared in the body of a table, one per line.
+/// The `indexes` and `Note` elements are skipped.
+fn scan_columns(body: &[Token]) -> Vec<ColumnDeclaration> {
+  let mut lines: Vec<&[Token]> = vec![];
+  let (mut start, mut depth) = (0, 0usize);
+
+  for (i, token) in body.iter().enumerate() {
+    match token.kind {
+      TokenKind::Punct('{' | '[' | '(') => depth += 1,
+      TokenKind::Punct('}' | ']' | ')') => depth = depth.saturating_sub(1),
+      TokenKind::Newline if depth == 0 => {
+        lines.push(&body[start..i]);
+        start = i + 1;
+      }
+      _ => (),
+    }
+  }
+  lines.push(&body[start..]);
+
+  lines
+    .into_iter()
+    .filter_map(|line| {
+      let (first, last) = (line.first()?, line.last()?);
+      let next = line.get(1).map(|token| token.kind);
+
+      match (first.kind, next) {
+        (TokenKind::Word, Some(TokenKind::Punct('{')))
+          if first.text.eq_ignore_ascii_case("indexes") || first.text.eq_ignore_ascii_case("note") =>
+        {
+          None
+        }
+        (TokenKind::Word, Some(TokenKind::Punct(':'))) if first.text.eq_ignore_ascii_case("note") => None,
+        (TokenKind::Word | TokenKind::Quoted, Some(_)) => Some(ColumnDeclaration {
+          name: first.text.to_string(),
+          span: first.span.start..last.span.end,
+        }),
+        _ => None,
+      }
+    })
+    .collect()
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum DiffOp {
+  Equal,
+  Delete,
+  Insert,
+}
+
+/// Creates a unified diff (with 3 lines of context) between two texts.
+/// Returns `None` when the texts are identical.
+pub fn unified_diff(old: &str, new: &str, old_name: &str, new_name: &str) -> Option<String> {
+  const CONTEXT: usize = 3;
+
+  if old == new {
+    return None;
+  }
+
+  let old_lines: Vec<_> = old.lines().collect();
+  let new_lines: Vec<_> = new.lines().collect();
+  let ops = diff_lines(&old_lines, &new_lines);
+
+  // line positions (0-based) of both sides before each op
+  let mut positions = Vec::with_capacity(ops.len() + 1);
+  let (mut old_pos, mut new_pos) = (0, 0);
+
+  for (op, _) in ops.iter() {
+    positions.push((old_pos, new_pos));
+
+    match op {
+      DiffOp::Equal => {
+        old_pos += 1;
+        new_pos += 1;
+      }
+      DiffOp::Delete => old_pos += 1,
+      DiffOp::Insert => new_pos += 1,
+    }
+  }
+
+  let changes: Vec<_> = ops
+    .iter()
+    .enumerate()
+    .filter(|(_, (op, _))| *op != DiffOp::Equal)
+    .map(|(i, _)| i)
+    .collect();
+
+  // group the changes that are close enough to share their context
+  let mut groups: Vec<(usize, usize)> = vec![];
+
+  for i in changes {
+    match groups.last_mut() {
+      Some((_, end)) if i - *end <= CONTEXT * 2 => *end = i,
+      _ => groups.push((i, i)),
+    }
+  }
+
+  let mut out = format!("--- {}\n+++ {}\n", old_name, new_name);
+
+  for (first, last) in groups {
+    let start = first.saturating_sub(CONTEXT);
+    let end = (last + CONTEXT + 1).min(ops.len());
+    let hunk = &ops[start..end];
+
+    let old_len = hunk.iter().filter(|(op, _)| *op != DiffOp::Insert).count();
+    let new_len = hunk.iter().filter(|(op, _)| *op != DiffOp::Delete).count();
+    let (old_start, new_start) = positions[start];
+
+    out.push_str(&format!(
+      "@@ -{},{} +{},{} @@\n",
+      old_start + usize::from(old_len > 0),
+      old_len,
+      new_start + usize::from(new_len > 0),
+      new_len
+    ));
+
+    for (op, line) in hunk {
+      let sign = match op {
+        DiffOp::Equal => ' ',
+        DiffOp::Delete => '-',
+        DiffOp::Insert => '+',
+      };
+
+      out.push_str(&format!("{}{}\n", sign, line));
+    }
+  }
+
+  Some(out)
+}
+
+/// Computes the line operations turning `old` into `new` based on their longest common subsequence.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(DiffOp, &'a str)> {
+  let prefix = old.iter().zip(new.iter()).take_while(|(a, b)| a == b).count();
+  let suffix = old[prefix..]
+    .iter()
+    .rev()
+    .zip(new[prefix..].iter().rev())
+    .take_while(|(a, b)| a == b)
+    .count();
+
+  let old_mid = &old[prefix..old.len() - suffix];
+  let new_mid = &new[prefix..new.len() - suffix];
+
+  // lcs[i][j] is the length of the LCS of `old_mid[i..]` and `new_mid[j..]`
+  let mut lcs = vec![vec![0usize; new_mid.len() + 1]; old_mid.len() + 1];
+
+  for i in (0..old_mid.len()).rev() {
+    for j in (0..new_mid.len()).rev() {
+      lcs[i][j] = if old_mid[i] == new_mid[j] {
+        lcs[i + 1][j + 1] + 1
+      } else {
+        lcs[i + 1][j].max(lcs[i][j + 1])
+      };
+    }
+  }
+
+  let mut ops: Vec<_> = old[..prefix].iter().map(|line| (DiffOp::Equal, *line)).collect();
+  let (mut i, mut j) = (0, 0);
+
+  while i < old_mid.len() || j < new_mid.len() {
+    if i < old_mid.len() && j < new_mid.len() && old_mid[i] == new_mid[j] {
+      ops.push((DiffOp::Equal, old_mid[i]));
+      i += 1;
+      j += 1;
+    } else if i < old_mid.len() && (j == new_mid.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+      ops.push((DiffOp::Delete, old_mid[i]));
+      i += 1;
+    } else {
+      ops.push((DiffOp::Insert, new_mid[j]));
+      j += 1;
+    }
+  }
+
+  ops.extend(old[old.len() - suffix..].iter().map(|line| (DiffOp::Equal, *line)));
+
+  ops
+}
+
+/// Checks whether the name matches the pattern, where `*` matches any sequence of characters.
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+  match pattern.split_once('*') {
+    None => pattern == name,
+    Some((prefix, rest)) => {
+      let name = match name.strip_prefix(prefix) {
+        Some(name) => name,
+        None => return false,
+      };
+
+      (0..=name.len())
+        .filter(|i| name.is_char_boundary(*i))
+        .any(|i| matches_pattern(rest, &name[i..]))
+    }
+  }
+}
+
+/// Finds the byte range of the `database_type` value (without the quotes) declared in the `Project` block.
+pub fn find_database_type(input: &str) -> Option<Range<usize>> {
+  let tokens = tokenize(input);
+  let mut i = 0;
+
+  // the project is found among the top-level blocks, whatever the layout of its body
+  let body = loop {
+    let token = tokens.get(i)?;
+
+    match token.kind {
+      TokenKind::Word if token.text.eq_ignore_ascii_case("project") => {
+        let body_start = find_token(&tokens, i, TokenKind::Punct('{'))?;
+        let body_end = matching_brace(&tokens, body_start);
+
+        break &tokens[body_start + 1..body_end.min(tokens.len())];
+      }
+      TokenKind::Word => i = skip_statement(&tokens, i + 1),
+      _ => i += 1,
+    }
+  };
+
+  let mut depth = 0usize;
+
+  for (i, token) in body.iter().enumerate() {
+    match token.kind {
+      TokenKind::Punct('{') => depth += 1,
+      TokenKind::Punct('}') => depth = depth.saturating_sub(1),
+      // the settings of the nested elements (e.g. `Note { .. }`) are skipped
+      TokenKind::Word if depth == 0 && token.text == "database_type" => match (body.get(i + 1), body.get(i + 2)) {
+        (Some(colon), Some(value))
+          if colon.kind == TokenKind::Punct(':')
+            && value.span.len() > 1
+            && (value.kind == TokenKind::Quoted || value.text.starts_with('\'') && !value.text.starts_with("'''")) =>
+        {
+          return Some(value.span.start + 1..value.span.end - 1)
+        }
+        _ => return None,
+      },
+      _ => (),
+    }
+  }
+
+  None
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+
+use super::err::Location;
+
+/// Kind of a construct that is accepted but not represented in the generated code.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum WarningCode {
+  /// An index defined by an expression.
+  ExprIndex,
+  /// The `headercolor` table setting.
+  HeaderColor,
+  /// A `TableGroup` block.
+  TableGroup,
+  /// The `database_type` of the `Project` block that differs from the configured target.
+  TargetMismatch,
+  /// A ref composition other than the first one of a composite ref.
+  CompositeRef,
+  /// A table without a primary key, which the backend cannot represent.
+  MissingPrimaryKey,
+  /// A default expression, which the targeted SeaORM release cannot declare in the entity.
+  DefaultExpr,
+}
+
+impl WarningCode {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::ExprIndex => "expr-index",
+      Self::HeaderColor => "header-color",
+      Self::TableGroup => "table-group",
+      Self::TargetMismatch => "target-mismatch",
+      Self::CompositeRef => "composite-ref",
+      Self::MissingPrimaryKey => "missing-primary-key",
+      Self::DefaultExpr => "default-expr",
+    }
+  }
+}
+
+impl fmt::Display for WarningCode {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CompilerWarning {
+  pub code: WarningCode,
+  pub message: String,
+  pub location: Location,
+}
+
+impl CompilerWarning {
+  pub fn new(code: WarningCode, message: impl ToString, location: Location) -> Self {
+    Self {
+      code,
+      message: message.to_string(),
+      location,
+    }
+  }
+
+  /// Resolves the line and column numbers of the warning against the given source.
+  pub fn with_source(mut self, input: &str) -> Self {
+    self.location.resolve(input);
+
+    self
+  }
+}
+
+impl fmt::Display for CompilerWarning {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "[{}] {} ({})", self.code, self.message, self.location)
+  }
+}
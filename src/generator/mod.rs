@@ -1,15 +1,15 @@
-#[derive(Debug, PartialEq, Clone)]
+use std::fmt;
+
+#[derive(Debug, PartialEq, Clone, Default)]
 pub struct Codegen {
   root_block: Block,
 }
 
 impl Codegen {
   pub fn new() -> Self {
-    let codegen = Self {
+    Self {
       root_block: Block::root(),
-    };
-
-    codegen
+    }
   }
 
   pub fn line(mut self, line_content: impl ToString) -> Self {
@@ -43,9 +43,9 @@ impl Codegen {
   }
 }
 
-impl ToString for Codegen {
-  fn to_string(&self) -> String {
-    self.root_block.to_string()
+impl fmt::Display for Codegen {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.root_block)
   }
 }
 
@@ -69,11 +69,7 @@ impl Block {
   }
 
   pub fn new(level: usize, before_block_content: Option<impl ToString>) -> Self {
-    let content = if let Some(impl_string) = before_block_content {
-      Some(impl_string.to_string())
-    } else {
-      None
-    };
+    let content = before_block_content.map(|impl_string| impl_string.to_string());
 
     Self {
       level,
@@ -119,7 +115,7 @@ impl Block {
 
   /// Inserts a block to the scope.
   pub fn block(mut self, block: Block) -> Self {
-    self.content = format!("{}{}\n", self.content, block.to_string());
+    self.content = format!("{}{}\n", self.content, block);
 
     self
   }
@@ -127,17 +123,17 @@ impl Block {
   /// Inserts a vector of blocks to the scope.
   pub fn block_vec(mut self, block_vec: Vec<Block>) -> Self {
     for block in block_vec.into_iter() {
-      self.content = format!("{}\n{}\n", self.content, block.to_string());
+      self.content = format!("{}\n{}\n", self.content, block);
     }
 
     self
   }
 }
 
-impl ToString for Block {
-  fn to_string(&self) -> String {
+impl fmt::Display for Block {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     let out = if self.level == 0 {
-      format!("{}", self.content.clone())
+      self.content.clone()
     } else {
       let block_indent = if self.level == 1 {
         String::new()
@@ -160,6 +156,6 @@ impl ToString for Block {
       }
     };
 
-    out
+    write!(f, "{}", out)
   }
 }
@@ -2,26 +2,114 @@
 #![forbid(clippy::all)]
 
 use std::env;
-use std::error::Error;
 use std::fs;
+use std::io;
 
+use compiler::err::CompilerError;
+use compiler::warn::CompilerWarning;
+
+pub mod build;
 pub mod compiler;
 pub mod generator;
 
+/// The DBML parser, whose semantic schema is handed to the backends.
+pub use dbml_rs;
+
 pub const DEFAULT_SCHEMA: &str = "public";
 pub const NAME: &str = env!("CARGO_PKG_NAME");
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-pub fn compile(config: compiler::config::Config) -> Result<(), Box<dyn Error>> {
+/// Reads the DBML file at `in_path` and writes the generated entities to `out_path` (or `OUT_DIR`).
+/// Returns the warnings raised during the compilation.
+pub fn compile(config: compiler::config::Config) -> Result<Vec<CompilerWarning>, CompilerError> {
+  if let Some(err_msg) = config.validate() {
+    return Err(CompilerError::Config {
+      message: err_msg.to_string(),
+    });
+  }
+
+  let out_path = config.resolve_out_path().unwrap_or_default();
+
+  let raw = fs::read_to_string(&config.in_path)?;
+
+  let output = compile_output(&raw, &config)?;
+
+  output.write(out_path)?;
+
+  Ok(output.warnings)
+}
+
+/// Regenerates the entities of the DBML file at `in_path` in memory and compares them with `out_path`.
+/// Returns the unified diff of every stale file. An empty list means that the files are up to date.
+pub fn check(config: compiler::config::Config) -> Result<Vec<String>, CompilerError> {
   if let Some(err_msg) = config.validate() {
-    return Err(err_msg.into());
+    return Err(CompilerError::Config {
+      message: err_msg.to_string(),
+    });
   }
 
-  let sem_ast = dbml_rs::parse_file(&config.in_path)?;
+  let out_path = config.resolve_out_path().unwrap_or_default();
+
+  let raw = fs::read_to_string(&config.in_path)?;
+
+  let output = compile_output(&raw, &config)?;
+
+  Ok(output.diff(out_path)?)
+}
+
+/// Compiles the DBML content and returns the generated source without touching the filesystem.
+/// The `in_path` and `out_path` of the configuration are ignored.
+/// The directory output mode is not supported, see `compile_output` instead.
+pub fn compile_str(dbml: &str, config: &compiler::config::Config) -> Result<String, CompilerError> {
+  let output = compile_output(dbml, config)?;
+
+  match output.code() {
+    Some(code) => Ok(code.to_string()),
+    None => Err(CompilerError::Config {
+      message: "the directory output mode cannot be compiled into a string".to_string(),
+    }),
+  }
+}
+
+/// Compiles the DBML content and returns the generated files along with the warnings.
+/// The `in_path` and `out_path` of the configuration are ignored.
+pub fn compile_output(
+  dbml: &str,
+  config: &compiler::config::Config,
+) -> Result<compiler::Output, CompilerError> {
+  let sem_ast = compiler::parse(dbml)?;
+
+  let output = compiler::compile(sem_ast, config).map_err(|err| err.with_source(dbml))?;
+
+  Ok(output.with_source(dbml))
+}
+
+/// Compiles the changes between the old and the new version of the DBML content into an incremental migration,
+/// with the backend of the configuration (sea-orm-migration by default).
+/// The `in_path` and `out_path` of the configuration are ignored, and the migration is a single file.
+pub fn compile_diff(
+  old_dbml: &str,
+  new_dbml: &str,
+  config: &compiler::config::Config,
+) -> Result<compiler::Output, CompilerError> {
+  let old_ast = compiler::parse(old_dbml)?;
+  let new_ast = compiler::parse(new_dbml)?;
+
+  let output = compiler::compile_diff(old_ast, new_ast, config).map_err(|err| err.with_source(new_dbml))?;
+
+  Ok(output.with_source(new_dbml))
+}
 
-  let result = compiler::compile(sem_ast, &config)?;
+/// Compiles the DBML content and writes the generated source into the given writer.
+/// The `in_path` and `out_path` of the configuration are ignored.
+pub fn compile_to_writer(
+  dbml: &str,
+  config: &compiler::config::Config,
+  mut writer: impl io::Write,
+) -> Result<(), CompilerError> {
+  let result = compile_str(dbml, config)?;
 
-  fs::write(config.out_path, result.as_bytes())?;
+  writer.write_all(result.as_bytes())?;
 
   Ok(())
 }
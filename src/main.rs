@@ -0,0 +1,393 @@
+#![forbid(unsafe_code)]
+#![forbid(clippy::all)]
+
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use sea_orm_dbml::compiler::backend::{self, Backend};
+use sea_orm_dbml::compiler::config::*;
+use sea_orm_dbml::compiler::err::CompilerError;
+use sea_orm_dbml::{compile_diff, compile_output, NAME, VERSION};
+
+const STDIO: &str = "-";
+
+const HELP: &str = "\
+Compiles DBML into SeaORM entities.
+
+Usage: sea-orm-dbml <COMMAND> [OPTIONS] [INPUT]
+
+Without an input, the inputs of the configuration file are compiled.
+
+Commands:
+  generate  Generates the entities
+  check     Checks that the DBML compiles, and that the output (if given) is up to date
+  lint      Reports the warnings and fails when there is any
+  diff      Generates the migration from the previous version of the DBML (see `--old`) to the input
+
+Options:
+  -c, --config <PATH>        Configuration file [default: sea-orm-dbml.toml, if present]
+  -i, --input <PATH>         Input DBML file, `-` reads from stdin [default: -]
+  -o, --output <PATH>        Output file or directory, `-` writes to stdout [default: -]
+  -b, --backend <NAME>       Code generator: sea-orm, sea-orm-migration, diesel, sqlx or sql [default: sea-orm]
+  -t, --target <TARGET>      Database entity target: postgres, mysql or sqlite [default: database_type of the project, or postgres]
+      --enum-type <TYPE>     Enum type: string, string(<len>) or integer [default: string]
+      --native-enum <BOOL>   Enable native enum for the database [default: true]
+      --pk-newtype <BOOL>    Wrap the primary keys in a newtype per table [default: false]
+      --output-mode <MODE>   Output layout: file or directory [default: file]
+      --sea-orm-version <V>  SeaORM release of the generated code: 0.11, 0.12 or 1 [default: 0.11]
+      --date-time-crate <C>  Date and time types: chrono, chrono-utc or time [default: chrono]
+      --decimal-crate <C>    Decimal type: rust_decimal or bigdecimal [default: rust_decimal]
+      --old <PATH>           Previous version of the input DBML file, compared by `diff`
+  -h, --help                 Prints help
+  -V, --version              Prints version";
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Command {
+  Generate,
+  Check,
+  Lint,
+  Diff,
+}
+
+#[derive(Debug)]
+struct Args {
+  command: Command,
+  config_path: Option<PathBuf>,
+  input: Option<String>,
+  output: Option<String>,
+  backend: Option<Arc<dyn Backend>>,
+  target: Option<Target>,
+  enum_type: Option<EnumType>,
+  native_enum: Option<bool>,
+  pk_newtype: Option<bool>,
+  output_mode: Option<OutputMode>,
+  sea_orm_version: Option<SeaOrmVersion>,
+  date_time_crate: Option<DateTimeCrate>,
+  decimal_crate: Option<DecimalCrate>,
+  old_input: Option<String>,
+}
+
+impl Args {
+  /// Creates the jobs to run from the configuration file (if any) and the command-line options.
+  /// The command-line options take precedence over the configuration file.
+  fn jobs(&self) -> Result<Vec<Job>, CompilerError> {
+    let config_path = match &self.config_path {
+      Some(path) => Some(path.clone()),
+      None if self.input.is_none() && Path::new(ConfigFile::FILE_NAME).is_file() => {
+        Some(PathBuf::from(ConfigFile::FILE_NAME))
+      }
+      None => None,
+    };
+
+    let (file, base_dir) = match &config_path {
+      Some(path) => (
+        ConfigFile::read(path)?,
+        path.parent().unwrap_or(Path::new("")).to_path_buf(),
+      ),
+      None => (ConfigFile::default(), PathBuf::new()),
+    };
+
+    let configs = match &self.input {
+      Some(input) => vec![file.apply(Config {
+        in_path: input.into(),
+        ..Default::default()
+      })?],
+      None if file.inputs.is_empty() => vec![file.apply(Config {
+        in_path: STDIO.into(),
+        ..Default::default()
+      })?],
+      None => file.configs(base_dir)?,
+    };
+
+    if self.output.is_some() && configs.len() > 1 {
+      return Err(CompilerError::Config {
+        message: "an output path cannot be given for multiple inputs".to_string(),
+      });
+    }
+    if self.old_input.is_some() && configs.len() > 1 {
+      return Err(CompilerError::Config {
+        message: "a previous version cannot be given for multiple inputs".to_string(),
+      });
+    }
+
+    Ok(
+      configs
+        .into_iter()
+        .map(|config| self.apply(config))
+        .map(|config| Job {
+          input: config.in_path.to_string_lossy().into_owned(),
+          output: match config.out_path.is_empty() {
+            true => STDIO.to_string(),
+            false => config.out_path.to_string_lossy().into_owned(),
+          },
+          config,
+        })
+        .collect(),
+    )
+  }
+
+  /// Applies the command-line options on top of the given configuration.
+  fn apply(&self, mut config: Config) -> Config {
+    if let Some(output) = &self.output {
+      config.out_path = output.into();
+    }
+    if let Some(backend) = &self.backend {
+      config.backend = Some(backend.clone());
+    }
+    if let Some(target) = &self.target {
+      config.target = Some(target.clone());
+    }
+    if let Some(enum_type) = &self.enum_type {
+      config.enum_type = enum_type.clone();
+    }
+    if let Some(is_native_enum) = self.native_enum {
+      config.is_native_enum = is_native_enum;
+    }
+    if let Some(is_pk_newtype) = self.pk_newtype {
+      config.is_pk_newtype = is_pk_newtype;
+    }
+    if let Some(output_mode) = &self.output_mode {
+      config.output_mode = output_mode.clone();
+    }
+    if let Some(sea_orm_version) = self.sea_orm_version {
+      config.sea_orm_version = sea_orm_version;
+    }
+    if let Some(date_time_crate) = self.date_time_crate {
+      config.date_time_crate = date_time_crate;
+    }
+    if let Some(decimal_crate) = self.decimal_crate {
+      config.decimal_crate = decimal_crate;
+    }
+
+    config
+  }
+}
+
+/// Compilation of a single input.
+#[derive(Debug)]
+struct Job {
+  input: String,
+  output: String,
+  config: Config,
+}
+
+impl Job {
+  fn input_name(&self) -> &str {
+    match self.input.as_str() {
+      STDIO => "<stdin>",
+      path => path,
+    }
+  }
+}
+
+fn main() -> ExitCode {
+  // the panics of the DBML analyzer are reported as errors by the compiler, so only the others are printed
+  let default_hook = panic::take_hook();
+  panic::set_hook(Box::new(move |info| {
+    if !matches!(info.location(), Some(location) if location.file().contains("dbml-rs")) {
+      default_hook(info)
+    }
+  }));
+
+  let args = match parse_args(env::args().skip(1)) {
+    Ok(Some(args)) => args,
+    Ok(None) => return ExitCode::SUCCESS,
+    Err(msg) => {
+      eprintln!("error: {}\n\n{}", msg, HELP);
+      return ExitCode::from(2);
+    }
+  };
+
+  let jobs = match args.jobs() {
+    Ok(jobs) => jobs,
+    Err(err) => {
+      eprintln!("error: {}", err);
+      return ExitCode::FAILURE;
+    }
+  };
+
+  let mut is_succeeded = true;
+
+  for job in jobs.iter() {
+    match run(&args, job) {
+      Ok(true) => (),
+      Ok(false) => is_succeeded = false,
+      Err(err) => {
+        eprintln!("error: {}: {}", job.input_name(), err);
+        is_succeeded = false;
+      }
+    }
+  }
+
+  match is_succeeded {
+    true => ExitCode::SUCCESS,
+    false => ExitCode::FAILURE,
+  }
+}
+
+/// Runs the command and returns whether it is succeeded.
+fn run(args: &Args, job: &Job) -> Result<bool, CompilerError> {
+  let raw = read_input(&job.input)?;
+
+  let output = match &args.old_input {
+    Some(old_input) if args.command == Command::Diff => {
+      compile_diff(&read_input(old_input)?, &raw, &job.config)?
+    }
+    _ => compile_output(&raw, &job.config)?,
+  };
+
+  for warning in output.warnings.iter() {
+    eprintln!("warning: {}: {}", job.input_name(), warning);
+  }
+
+  match args.command {
+    Command::Generate | Command::Diff => {
+      match (job.output.as_str(), output.code()) {
+        (STDIO, Some(code)) => io::stdout().write_all(code.as_bytes())?,
+        (STDIO, None) => {
+          return Err(CompilerError::Config {
+            message: "the directory output mode requires an output path".to_string(),
+          })
+        }
+        (path, _) => output.write(path)?,
+      };
+
+      Ok(true)
+    }
+    Command::Check => match job.output.as_str() {
+      STDIO => Ok(true),
+      path => {
+        let diffs = output.diff(path)?;
+
+        for diff in diffs.iter() {
+          print!("{}", diff);
+        }
+
+        if !diffs.is_empty() {
+          eprintln!("error: {} is not up to date", path);
+        }
+
+        Ok(diffs.is_empty())
+      }
+    },
+    Command::Lint => Ok(output.warnings.is_empty()),
+  }
+}
+
+/// Reads a DBML file, or stdin.
+fn read_input(input: &str) -> io::Result<String> {
+  match input {
+    STDIO => {
+      let mut raw = String::new();
+      io::stdin().read_to_string(&mut raw)?;
+      Ok(raw)
+    }
+    path => fs::read_to_string(path),
+  }
+}
+
+/// Parses the command-line arguments. Returns `None` when only help or version is requested.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+  let command = match args.next().as_deref() {
+    Some("generate") => Command::Generate,
+    Some("check") => Command::Check,
+    Some("lint") => Command::Lint,
+    Some("diff") => Command::Diff,
+    Some("-h" | "--help") => {
+      println!("{}", HELP);
+      return Ok(None);
+    }
+    Some("-V" | "--version") => {
+      println!("{} {}", NAME, VERSION);
+      return Ok(None);
+    }
+    Some(command) => return Err(format!("unknown command '{}'", command)),
+    None => return Err("missing command".to_string()),
+  };
+
+  let mut out = Args {
+    command,
+    config_path: None,
+    input: None,
+    output: None,
+    backend: None,
+    target: None,
+    enum_type: None,
+    native_enum: None,
+    pk_newtype: None,
+    output_mode: None,
+    sea_orm_version: None,
+    date_time_crate: None,
+    decimal_crate: None,
+    old_input: None,
+  };
+
+  while let Some(arg) = args.next() {
+    let (flag, inline_value) = match arg.split_once('=') {
+      Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+      _ => (arg.clone(), None),
+    };
+
+    let mut value = || {
+      inline_value
+        .clone()
+        .or_else(|| args.next())
+        .ok_or_else(|| format!("missing value for '{}'", flag))
+    };
+
+    match flag.as_str() {
+      "-c" | "--config" => out.config_path = Some(value()?.into()),
+      "-i" | "--input" => out.input = Some(value()?),
+      "-o" | "--output" => out.output = Some(value()?),
+      "-b" | "--backend" => {
+        let name = value()?;
+        out.backend =
+          Some(backend::from_name(&name).ok_or_else(|| format!("'{}' backend is not supported!", name))?)
+      }
+      "-t" | "--target" => out.target = Some(value()?.parse()?),
+      "--enum-type" => out.enum_type = Some(value()?.parse()?),
+      "--output-mode" => out.output_mode = Some(value()?.parse()?),
+      "--sea-orm-version" => out.sea_orm_version = Some(value()?.parse()?),
+      "--date-time-crate" => out.date_time_crate = Some(value()?.parse()?),
+      "--decimal-crate" => out.decimal_crate = Some(value()?.parse()?),
+      "--old" => out.old_input = Some(value()?),
+      "--native-enum" => {
+        out.native_enum = Some(
+          value()?
+            .parse()
+            .map_err(|_| "'--native-enum' expects true or false".to_string())?,
+        )
+      }
+      "--pk-newtype" => {
+        out.pk_newtype = Some(
+          value()?
+            .parse()
+            .map_err(|_| "'--pk-newtype' expects true or false".to_string())?,
+        )
+      }
+      "-h" | "--help" => {
+        println!("{}", HELP);
+        return Ok(None);
+      }
+      _ if !flag.starts_with('-') || flag == STDIO => {
+        if out.input.is_some() {
+          return Err(format!("unexpected argument '{}'", arg));
+        }
+
+        out.input = Some(arg);
+      }
+      _ => return Err(format!("unknown option '{}'", flag)),
+    }
+  }
+
+  if out.command == Command::Diff && out.old_input.is_none() {
+    return Err("'diff' requires the previous version of the input with '--old'".to_string());
+  }
+
+  Ok(Some(out))
+}
@@ -0,0 +1,21 @@
+//! Compiles the schema and the models generated for Diesel 2.
+
+#[path = "versions/diesel.rs"]
+mod diesel_schema;
+
+#[test]
+fn select_query() {
+  use diesel::prelude::*;
+  use diesel_schema::models::{Order, OrderStatus};
+  use diesel_schema::schema::{orders, users};
+
+  let query = orders::table
+    .inner_join(users::table)
+    .filter(orders::status.eq(OrderStatus::Paid))
+    .select(Order::as_select());
+  let sql = diesel::debug_query::<diesel::pg::Pg, _>(&query).to_string();
+
+  assert!(sql.starts_with("SELECT \"orders\".\"id\", \"orders\".\"user_id\""));
+  assert!(sql.contains("INNER JOIN \"users\" ON (\"orders\".\"user_id\" = \"users\".\"id\")"));
+  assert!(sql.ends_with("-- binds: [Paid]"));
+}
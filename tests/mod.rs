@@ -5,10 +5,81 @@ use std::{
   ffi::OsString,
 };
 
-use sea_orm_dbml::{compiler::config::Config, *};
+use sea_orm_dbml::{
+  compiler::{config::Config, err::CompilerError, warn::WarningCode},
+  *,
+};
+
+/// The entities and the migrations generated for SeaORM 0.11, which is the dev-dependency `sea-orm`.
+/// The other releases are compiled by their own test targets.
+#[path = "versions/v0_11.rs"]
+mod sea_orm_0_11_entities;
+
+#[path = "versions/migration_v0_11.rs"]
+mod sea_orm_0_11_migration;
+
+#[path = "versions/migration_diff_v0_11.rs"]
+mod sea_orm_0_11_migration_diff;
 
 const DBML_DIR: &str = "tests/dbml";
 const OUT_DIR: &str = "tests/out";
+const VERSIONS_DIR: &str = "tests/versions";
+
+/// Fixtures that are expected to be rejected by `gen_all`, whose errors are asserted by `compile_rejected_fixtures`.
+/// Some fixtures are shared with the parser and contain constructs rejected by the compiler.
+const REJECTED_FIXTURES: [&str; 9] = [
+  // a multi-dimensional array
+  "array_type.in.dbml",
+  // the primary key is declared on a column and in the indexes
+  "composite_pk.in.dbml",
+  // a null default
+  "default_tables.in.dbml",
+  // a column declared in an enum
+  "enum_tables.in.dbml",
+  // upstream limitation: the analyzer of dbml-rs compares the refs by their left-hand side as written,
+  // so the two refs from `countries.code` are taken as duplicates
+  "general_schema.in.dbml",
+  // an unknown column type
+  "header_color_tables.in.dbml",
+  // the same upstream limitation as `general_schema.in.dbml`, the schema is otherwise accepted
+  "project.in.dbml",
+  // an enum which is not declared
+  "referential_actions.in.dbml",
+  // the primary key is declared on two columns
+  "table_element.in.dbml",
+];
+
+/// Asserts the `#[sea_orm(..)]` attributes and the Rust type of a field of the generated entities,
+/// regardless of the layout of the generated code.
+fn assert_entity_field(out: &str, module: &str, field: &str, attrs: &str, rust_type: &str) {
+  let mut current_module = None;
+  let mut current_attrs = "";
+
+  for line in out.lines().map(str::trim) {
+    if let Some(name) = line.strip_prefix("pub mod ").and_then(|rest| rest.strip_suffix(" {")) {
+      current_module = Some(name);
+    }
+
+    let field_decl = line
+      .strip_prefix("pub ")
+      .and_then(|rest| rest.strip_suffix(','))
+      .and_then(|rest| rest.split_once(": "));
+
+    if let Some((name, ty)) = field_decl {
+      if current_module == Some(module) && name == field {
+        assert_eq!((current_attrs, ty), (attrs, rust_type), "field '{}' of module '{}'", field, module);
+        return;
+      }
+    }
+
+    current_attrs = line
+      .strip_prefix("#[sea_orm(")
+      .and_then(|rest| rest.strip_suffix(")]"))
+      .unwrap_or_default();
+  }
+
+  panic!("field '{}' of module '{}' is not generated", field, module);
+}
 
 fn read_dbml_dir<P: AsRef<Path>>(dir_path: P) -> Result<Vec<PathBuf>> {
   let mut out = vec![];
@@ -26,7 +97,7 @@ fn read_dbml_dir<P: AsRef<Path>>(dir_path: P) -> Result<Vec<PathBuf>> {
 }
 
 fn create_out_dir() -> Result<()> {
-  if !fs::metadata(OUT_DIR).is_ok() {
+  if fs::metadata(OUT_DIR).is_err() {
     fs::create_dir(OUT_DIR)?;
   }
 
@@ -47,12 +118,1736 @@ fn gen_all() -> Result<()> {
     let res = compile(Config {
       in_path: OsString::from(&path),
       out_path: OsString::from(format!("{}/{}", OUT_DIR, out_file_name)),
-      target: compiler::config::Target::Postgres,
+      target: Some(compiler::config::Target::Postgres),
       ..Default::default()
     });
-    
-    res.unwrap_or_else(|err| panic!("{:?} {}", path, err));
+
+    let file_name = path.file_name().unwrap().to_str().unwrap();
+
+    match (file_name, res) {
+      (name, Ok(_)) if !REJECTED_FIXTURES.contains(&name) => (),
+      (name, Err(_)) if REJECTED_FIXTURES.contains(&name) => (),
+      (_, res) => panic!("{:?} {:?}", path, res.err()),
+    }
   }
 
   Ok(())
-}
\ No newline at end of file
+}
+
+#[test]
+fn compile_rejected_fixtures() -> Result<()> {
+  let config = Config {
+    target: Some(compiler::config::Target::Postgres),
+    ..Default::default()
+  };
+
+  for file_name in REJECTED_FIXTURES {
+    let dbml = fs::read_to_string(format!("{}/{}", DBML_DIR, file_name))?;
+
+    let err = match compile_str(&dbml, &config) {
+      Ok(_) => panic!("{} is accepted", file_name),
+      Err(err) => err,
+    };
+
+    let line_col = match (file_name, &err) {
+      ("array_type.in.dbml", CompilerError::UnsupportedType { type_name, location })
+        if type_name == "text[][]" => location.line_col,
+      ("header_color_tables.in.dbml", CompilerError::UnsupportedType { type_name, location })
+        if type_name == "date_time" => location.line_col,
+      ("referential_actions.in.dbml", CompilerError::UnsupportedType { type_name, location })
+        if type_name == "orders_status_enum" => location.line_col,
+      (
+        "composite_pk.in.dbml" | "table_element.in.dbml",
+        CompilerError::Duplicated { kind: "primary key", name, location },
+      ) if name == "users" => location.line_col,
+      (
+        "general_schema.in.dbml" | "project.in.dbml",
+        CompilerError::Duplicated { kind: "ref", name, location },
+      ) if name == "countries.code" => location.line_col,
+      ("default_tables.in.dbml" | "enum_tables.in.dbml", CompilerError::Parse { location, .. }) => location.line_col,
+      _ => panic!("{} {:?}", file_name, err),
+    };
+
+    let expected = match file_name {
+      "array_type.in.dbml" => (8, 3),
+      "composite_pk.in.dbml" => (1, 1),
+      "default_tables.in.dbml" => (20, 3),
+      "enum_tables.in.dbml" => (6, 18),
+      "general_schema.in.dbml" => (74, 3),
+      "header_color_tables.in.dbml" => (9, 2),
+      "project.in.dbml" => (81, 3),
+      "referential_actions.in.dbml" => (8, 3),
+      "table_element.in.dbml" => (5, 1),
+      _ => unreachable!(),
+    };
+
+    assert_eq!(line_col, Some(expected), "{} {}", file_name, err);
+  }
+
+  Ok(())
+}
+
+#[test]
+fn compile_in_memory() -> Result<()> {
+  let dbml = fs::read_to_string(format!("{}/sample_1.dbml", DBML_DIR))?;
+  let config = Config {
+    target: Some(compiler::config::Target::Postgres),
+    ..Default::default()
+  };
+
+  let out = compile_str(&dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  let mut buf = vec![];
+  compile_to_writer(&dbml, &config, &mut buf).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.starts_with(&format!("//! Generated by {} {}", NAME, VERSION)));
+  assert!(out.contains("pub mod users {"));
+  assert_eq!(out.as_bytes(), buf.as_slice());
+
+  Ok(())
+}
+
+#[test]
+fn compile_errors() {
+  let config = Config::default();
+
+  let dbml = r#"Project p {
+  database_type: 'PostgreSQL'
+}
+
+Table t {
+  id int [pk]
+  doc geometry
+}"#;
+
+  match compile_str(dbml, &config).unwrap_err() {
+    CompilerError::UnsupportedType { type_name, location } => {
+      assert_eq!(type_name, "geometry");
+      assert_eq!(location.column.as_deref(), Some("doc"));
+      assert_eq!(location.line_col, Some((7, 3)));
+    }
+    err => panic!("unexpected error: {}", err),
+  }
+
+  let dbml = r#"Project p {
+  database_type: 'PostgreSQL'
+}
+
+Table a {
+  id int [pk]
+}
+
+Table b {
+  id int [pk, ref: <> a.id]
+}"#;
+
+  match compile_str(dbml, &config).unwrap_err() {
+    CompilerError::UnsupportedRelation { .. } => (),
+    err => panic!("unexpected error: {}", err),
+  }
+
+  match compile_str("Table t {", &config).unwrap_err() {
+    CompilerError::Parse { location, .. } => assert!(location.line_col.is_some()),
+    err => panic!("unexpected error: {}", err),
+  }
+
+  // the locations come from the declarations, not from the comments, the notes or the tables of other schemas
+  let dbml = r#"// Table b { id int }
+Table a { id int [pk, note: 'Table b'] }
+Table s.b {
+  id int [pk]
+}
+Table b {
+  id int [pk]
+  doc geometry
+}"#;
+
+  match compile_str(dbml, &config).unwrap_err() {
+    CompilerError::UnsupportedType { location, .. } => assert_eq!(location.line_col, Some((8, 3))),
+    err => panic!("unexpected error: {}", err),
+  }
+
+  // the rejections of the analyzer are reported with their location
+  let dbml = r#"Table a {
+  id int [pk]
+  code int [pk]
+}"#;
+
+  match compile_str(dbml, &config).unwrap_err() {
+    CompilerError::Duplicated { kind, name, location } => {
+      assert_eq!((kind, name.as_str()), ("primary key", "a"));
+      assert_eq!(location.line_col, Some((1, 1)));
+    }
+    err => panic!("unexpected error: {}", err),
+  }
+
+  let dbml = r#"Table a {
+  id int [pk]
+}
+
+Table b {
+  id int [pk]
+  a_id int
+}
+
+Ref: b.a_id > a.code"#;
+
+  match compile_str(dbml, &config).unwrap_err() {
+    CompilerError::UnresolvedColumn { name, location } => {
+      assert_eq!(name, "a.code");
+      assert_eq!(location.line_col, Some((7, 3)));
+    }
+    err => panic!("unexpected error: {}", err),
+  }
+
+  match compile_str("Table a {\n  id int [pk, ref: > c.id]\n}", &config).unwrap_err() {
+    CompilerError::UnresolvedTable { name, location } => {
+      assert_eq!(name, "c");
+      assert_eq!(location.line_col, Some((2, 3)));
+    }
+    err => panic!("unexpected error: {}", err),
+  }
+}
+
+#[test]
+fn compile_warnings() {
+  let dbml = r#"Project p {
+  database_type: 'PostgreSQL'
+  Note: 'Project note'
+}
+
+Table a [headercolor: #fff] {
+  id int [pk]
+  name varchar
+
+  indexes {
+    `lower(name)`
+  }
+}
+
+Table b {
+  id int [pk]
+  a_id int
+  a_name varchar
+}
+
+TableGroup g {
+  a
+  b
+}
+
+Ref: b.(a_id, a_name) > a.(id, name)"#;
+
+  let output = compile_output(dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err));
+
+  let codes: Vec<_> = output.warnings.iter().map(|warning| warning.code).collect();
+
+  assert_eq!(
+    codes,
+    vec![
+      WarningCode::HeaderColor,
+      WarningCode::TableGroup,
+      WarningCode::CompositeRef,
+      WarningCode::ExprIndex,
+    ]
+  );
+  assert_eq!(output.warnings[0].location.line_col, Some((6, 1)));
+  assert_eq!(output.warnings[1].location.line_col, Some((21, 1)));
+  assert_eq!(output.warnings[2].location.line_col, Some((17, 3)));
+
+  // the expression indexes are kept by the SQL output
+  let config = Config {
+    backend: compiler::backend::from_name("sql"),
+    ..Default::default()
+  };
+  let output = compile_output(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(output.warnings.iter().all(|warning| warning.code != WarningCode::ExprIndex));
+}
+
+#[test]
+fn compile_project() {
+  use compiler::config::Target;
+
+  let dbml = r#"Project ecommerce {
+  note: '''
+    # Introduction
+    This is an ecommerce project
+
+    # Description
+    ...
+  '''
+  database_type: 'PostgreSQL'
+}
+
+Table products {
+  id int [pk]
+}"#;
+
+  let output = compile_output(dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err));
+  assert!(output.warnings.is_empty());
+  assert!(output.code().unwrap().contains(
+    "//!\n//! # Introduction\n//! This is an ecommerce project\n//!\n//! # Description\n//! ...\n\n"
+  ));
+
+  let output = compile_output(&dbml.replace("'PostgreSQL'", "'Oracle'"), &Config::default())
+    .unwrap_or_else(|err| panic!("{}", err));
+  assert_eq!(output.warnings.len(), 1);
+  assert_eq!(output.warnings[0].code, WarningCode::TargetMismatch);
+  assert_eq!(output.warnings[0].location.line_col, Some((1, 1)));
+
+  let dbml = dbml.replace("'PostgreSQL'", "'mysql'");
+
+  let output = compile_output(&dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err));
+  assert!(output.warnings.is_empty());
+
+  let config = Config {
+    target: Some(Target::Postgres),
+    ..Default::default()
+  };
+  let output = compile_output(&dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+  assert_eq!(output.warnings[0].code, WarningCode::TargetMismatch);
+  assert!(output.warnings[0].message.contains("does not match"));
+}
+
+#[test]
+fn compile_project_layouts() {
+  // the database type is found whatever the layout of the project block
+  for dbml in [
+    "Project shop { database_type: 'MySQL' }\n\nTable products {\n  id int [pk]\n}",
+    r#"Project shop {
+  Note {
+    'Shop of the products'
+  }
+  database_type: 'MySQL'
+}
+
+Table products {
+  id int [pk]
+}"#,
+  ] {
+    let output = compile_output(dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err));
+
+    assert!(output.warnings.is_empty());
+    assert!(output.code().unwrap().contains("#[sea_orm(table_name = \"products\")]\n"));
+  }
+
+  // a database type in a note is not the one of the project
+  let dbml = r#"Project shop {
+  Note: 'database_type: MySQL'
+}
+
+Table products {
+  id int [pk]
+}"#;
+
+  let out = compile_str(dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err));
+  assert!(out.contains("#[sea_orm(table_name = \"products\", schema_name = \"public\")]\n"));
+}
+
+#[test]
+fn compile_mysql() {
+  let dbml = r#"Project shop {
+  database_type: 'MySQL'
+}
+
+Enum orders_status {
+  created
+  done
+}
+
+Table orders {
+  id "int unsigned" [pk, increment]
+  user_id "BIGINT UNSIGNED" [not null]
+  quantity int(11) [default: 1]
+  status orders_status
+  created_at datetime [default: `now()`]
+}"#;
+
+  let output = compile_output(dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err));
+  let out = output.code().unwrap();
+
+  assert_eq!(output.warnings.len(), 1);
+  assert_eq!(output.warnings[0].code, WarningCode::DefaultExpr);
+  assert!(output.warnings[0].message.contains("CURRENT_TIMESTAMP"));
+  assert!(out.contains("#[sea_orm(table_name = \"orders\")]\n"));
+  assert_entity_field(out, "orders", "id", "column_type = \"Unsigned\", primary_key", "u32");
+  assert_entity_field(out, "orders", "user_id", "column_type = \"BigUnsigned\"", "u64");
+  assert_entity_field(out, "orders", "quantity", "column_type = \"Integer\", default_value = 1", "i32");
+  assert_entity_field(out, "orders", "created_at", "column_type = \"DateTime\"", "DateTime");
+  assert!(out.contains(
+    "#[sea_orm(rs_type = \"String\", db_type = \"Enum\", enum_name = \"orders_status\")]"
+  ));
+
+  let config = Config {
+    sea_orm_version: compiler::config::SeaOrmVersion::V1,
+    ..Default::default()
+  };
+  let output = compile_output(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+  let out = output.code().unwrap();
+
+  assert!(output.warnings.is_empty());
+  assert_entity_field(
+    out,
+    "orders",
+    "created_at",
+    r#"column_type = "DateTime", default_expr = "Expr::cust(\"CURRENT_TIMESTAMP\")""#,
+    "DateTime",
+  );
+
+  let config = Config {
+    target: Some(compiler::config::Target::Postgres),
+    ..Default::default()
+  };
+
+  match compile_str(dbml, &config).unwrap_err() {
+    CompilerError::UnsupportedType { type_name, .. } => assert_eq!(type_name, "int unsigned"),
+    err => panic!("unexpected error: {}", err),
+  }
+}
+
+#[test]
+fn compile_column_types() {
+  use compiler::config::{SeaOrmVersion, Target};
+
+  let dbml = r#"Table t {
+  id serial [pk]
+  doc jsonb
+  ip inet
+  mac macaddr
+  price money
+  span interval
+  flag bit
+  mask "bit varying(16)"
+  body xml
+  at "time with time zone"
+  at_second time(1)
+  weight float
+  title string
+  name nvarchar(32)
+  amount decimal(10)
+  photo bytea
+  thumb blob
+}"#;
+
+  let out = compile_str(dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err));
+
+  assert_entity_field(&out, "t", "id", "column_type = \"Integer\", primary_key", "i32");
+  assert_entity_field(&out, "t", "doc", "column_type = \"JsonBinary\"", "Json");
+  assert_entity_field(&out, "t", "ip", "column_type = \"Inet\"", "String");
+  assert_entity_field(&out, "t", "mac", "column_type = \"MacAddr\"", "String");
+  assert_entity_field(&out, "t", "price", "column_type = \"Money(None)\"", "Decimal");
+  assert_entity_field(&out, "t", "span", "column_type = \"Interval(None, None)\"", "String");
+  assert_entity_field(&out, "t", "flag", "column_type = \"Bit(None)\"", "bool");
+  assert_entity_field(&out, "t", "mask", "column_type = \"VarBit(16)\"", "Vec<u8>");
+  assert_entity_field(&out, "t", "body", "column_type = \"custom(\\\"xml\\\")\"", "String");
+  assert_entity_field(&out, "t", "at", "column_type = \"custom(\\\"timetz\\\")\"", "String");
+  assert_entity_field(&out, "t", "at_second", "column_type = \"Time\"", "Time");
+  assert_entity_field(&out, "t", "weight", "column_type = \"Double\"", "f64");
+  assert_entity_field(&out, "t", "title", "column_type = \"String(None)\"", "String");
+  assert_entity_field(&out, "t", "name", "column_type = \"String(Some(32))\"", "String");
+  assert_entity_field(&out, "t", "amount", "column_type = \"Decimal(Some((10, 0)))\"", "Decimal");
+  assert_entity_field(&out, "t", "photo", "column_type = \"Binary(BlobSize::Blob(None))\"", "Vec<u8>");
+
+  let config = Config {
+    sea_orm_version: SeaOrmVersion::V1,
+    ..Default::default()
+  };
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert_entity_field(&out, "t", "ip", "column_type = \"Inet\"", "IpNetwork");
+  assert_entity_field(&out, "t", "photo", "column_type = \"VarBinary(StringLen::None)\"", "Vec<u8>");
+  assert_entity_field(&out, "t", "thumb", "column_type = \"Blob\"", "Vec<u8>");
+
+  let config = Config {
+    backend: compiler::backend::from_name("sql"),
+    ..Default::default()
+  };
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("\t\"id\" serial NOT NULL PRIMARY KEY,\n"));
+  assert!(out.contains("\t\"mask\" varbit(16) NOT NULL,\n"));
+  assert!(out.contains("\t\"at_second\" time(1) NOT NULL,\n"));
+  assert!(out.contains("\t\"weight\" double precision NOT NULL,\n"));
+  assert!(out.contains("\t\"thumb\" bytea NOT NULL\n"));
+
+  let config = Config {
+    backend: compiler::backend::from_name("sea-orm-migration"),
+    ..Default::default()
+  };
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains(".col(ColumnDef::new(T::Doc).json_binary().not_null())\n"));
+  assert!(out.contains(".col(ColumnDef::new(T::Body).custom(Alias::new(\"xml\")).not_null())\n"));
+  assert!(out.contains(".col(ColumnDef::new(T::Thumb).blob(BlobSize::Blob(None)).not_null())\n"));
+
+  let config = Config {
+    target: Some(Target::MySql),
+    ..Default::default()
+  };
+
+  match compile_str(dbml, &config).unwrap_err() {
+    CompilerError::UnsupportedFeature { feature, location, .. } => {
+      assert_eq!(feature, "'inet' column type");
+      assert_eq!(location.line_col, Some((4, 3)));
+    }
+    err => panic!("unexpected error: {}", err),
+  }
+
+  let out = compile_str("Table t {\n  weight float\n  data varbinary(16)\n}", &config)
+    .unwrap_or_else(|err| panic!("{}", err));
+
+  assert_entity_field(&out, "t", "weight", "column_type = \"Float\"", "f32");
+  assert_entity_field(&out, "t", "data", "column_type = \"VarBinary(16)\"", "Vec<u8>");
+}
+
+#[test]
+fn compile_rust_type_annotations() {
+  let dbml = r#"Table t {
+  id int [pk]
+  tags json [note: '@rust_type: Vec<crate::Tag>']
+  email varchar(320) [null, note: '''
+    Contact address
+    @rust_type: crate::Email
+  ''']
+}"#;
+
+  let out = compile_str(dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err));
+
+  assert_entity_field(&out, "t", "tags", "column_type = \"Json\"", "Vec<crate::Tag>");
+  assert_entity_field(&out, "t", "email", "column_type = \"String(Some(320))\", nullable", "Option<crate::Email>");
+
+  let config = Config {
+    backend: compiler::backend::from_name("sqlx"),
+    ..Default::default()
+  };
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("pub tags: Vec<crate::Tag>,"));
+  assert!(out.contains("pub email: Option<crate::Email>,"));
+}
+
+#[test]
+fn compile_pk_newtypes() {
+  let dbml = r#"Table users {
+  id int [pk, increment]
+  referrer_id int [null]
+}
+
+Table posts {
+  id uuid [pk]
+  user_id int
+}
+
+Table tags {
+  post_id uuid
+  name varchar
+
+  indexes {
+    (post_id, name) [pk]
+  }
+}
+
+Ref: users.referrer_id > users.id
+Ref: posts.user_id > users.id
+Ref: tags.post_id > posts.id"#;
+
+  let out = compile_str(dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(!out.contains("UsersId"));
+
+  let config = Config {
+    is_pk_newtype: true,
+    ..Default::default()
+  };
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("pub struct UsersId(pub i32);"));
+  assert!(out.contains("pub struct PostsId(pub Uuid);"));
+  assert!(out.contains("impl sea_orm::TryGetable for UsersId {"));
+  assert!(out.contains("impl sea_orm::sea_query::ValueType for UsersId {"));
+  assert!(out.contains("impl sea_orm::sea_query::Nullable for UsersId {"));
+  assert_entity_field(&out, "users", "id", "column_type = \"Integer\", primary_key", "UsersId");
+  assert!(out.contains("pub referrer_id: Option<UsersId>,"));
+  assert!(out.contains("pub user_id: super::users::UsersId,"));
+  // the composite keys are not wrapped, but their foreign keys are
+  assert!(!out.contains("pub struct TagsPostId"));
+  assert!(out.contains("pub post_id: super::posts::PostsId,"));
+  assert!(out.contains("pub name: String,"));
+}
+
+#[test]
+fn compile_auto_increment() {
+  use compiler::config::Target;
+
+  let dbml = r#"Table a {
+  id int [pk, increment]
+}
+
+Table b {
+  id bigserial [pk]
+}
+
+Table c {
+  id int [pk, default: `generated by default as identity`]
+}
+
+Table d {
+  id uuid [pk, increment]
+}
+
+Table e {
+  code varchar [pk]
+}
+
+Table f {
+  a_id serial
+  b_id int
+
+  indexes {
+    (a_id, b_id) [pk]
+  }
+}"#;
+
+  let out = compile_str(dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err));
+
+  assert_entity_field(&out, "a", "id", "column_type = \"Integer\", primary_key", "i32");
+  assert_entity_field(&out, "b", "id", "column_type = \"BigInteger\", primary_key", "i64");
+  assert!(!out.contains("default_expr"));
+  assert_entity_field(&out, "d", "id", "column_type = \"Uuid\", primary_key, auto_increment = false", "Uuid");
+  assert_entity_field(&out, "e", "code", "column_type = \"String(None)\", primary_key, auto_increment = false", "String");
+  assert_entity_field(&out, "f", "a_id", "column_type = \"Integer\", primary_key, auto_increment = false", "i32");
+  assert_entity_field(&out, "f", "b_id", "column_type = \"Integer\", primary_key, auto_increment = false", "i32");
+
+  let config = Config {
+    backend: compiler::backend::from_name("sea-orm-migration"),
+    ..Default::default()
+  };
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains(".col(ColumnDef::new(B::Id).big_integer().not_null().auto_increment().primary_key())\n"));
+  assert!(out.contains(".col(ColumnDef::new(C::Id).integer().not_null().auto_increment().primary_key())\n"));
+  assert!(out.contains(".col(ColumnDef::new(D::Id).uuid().not_null().primary_key())\n"));
+
+  let config = Config {
+    backend: compiler::backend::from_name("sql"),
+    ..Default::default()
+  };
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("\t\"id\" serial NOT NULL PRIMARY KEY\n"));
+  assert!(out.contains("\t\"id\" integer NOT NULL PRIMARY KEY generated by default as identity\n"));
+
+  let config = Config {
+    backend: compiler::backend::from_name("sql"),
+    target: Some(Target::MySql),
+    ..Default::default()
+  };
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("\t`id` bigint NOT NULL PRIMARY KEY AUTO_INCREMENT\n"));
+  assert!(out.contains("\t`id` int NOT NULL PRIMARY KEY AUTO_INCREMENT\n"));
+}
+
+#[test]
+fn compile_date_time_and_decimal_crates() {
+  use compiler::config::{ConfigFile, DateTimeCrate, DecimalCrate};
+
+  let dbml = r#"Table t {
+  id int [pk]
+  day date
+  at time
+  created_at timestamp
+  updated_at timestamptz
+  price decimal(10, 2)
+}"#;
+
+  let config = ConfigFile::parse("date_time_crate = \"time\"\ndecimal_crate = \"bigdecimal\"")
+    .and_then(|file| file.apply(Config::default()))
+    .unwrap_or_else(|err| panic!("{}", err));
+  assert_eq!(config.date_time_crate, DateTimeCrate::Time);
+  assert_eq!(config.decimal_crate, DecimalCrate::BigDecimal);
+
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert_entity_field(&out, "t", "day", "column_type = \"Date\"", "TimeDate");
+  assert_entity_field(&out, "t", "at", "column_type = \"Time\"", "TimeTime");
+  assert_entity_field(&out, "t", "created_at", "column_type = \"DateTime\"", "TimeDateTime");
+  assert_entity_field(&out, "t", "updated_at", "column_type = \"TimestampWithTimeZone\"", "TimeDateTimeWithTimeZone");
+  assert_entity_field(&out, "t", "price", "column_type = \"Decimal(Some((10, 2)))\"", "BigDecimal");
+
+  let out = compile_str(
+    dbml,
+    &Config {
+      backend: compiler::backend::from_name("sqlx"),
+      ..config
+    },
+  )
+  .unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("pub created_at: time::PrimitiveDateTime,"));
+  assert!(out.contains("pub updated_at: time::OffsetDateTime,"));
+  assert!(out.contains("pub price: bigdecimal::BigDecimal,"));
+
+  let config = Config {
+    date_time_crate: DateTimeCrate::ChronoUtc,
+    ..Default::default()
+  };
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("pub created_at: DateTime,"));
+  assert!(out.contains("pub updated_at: DateTimeUtc,"));
+  assert!(out.contains("pub price: Decimal,"));
+
+  assert!("jiff".parse::<DateTimeCrate>().is_err());
+}
+
+#[test]
+fn compile_array_types() {
+  use compiler::config::{SeaOrmVersion, Target};
+
+  let dbml = r#"Table t {
+  id int [pk]
+  tags "varchar(16)[]"
+  grid "integer[3][3]"
+}"#;
+
+  // the drivers cannot decode the multi-dimensional arrays into the generated types
+  for backend in ["sea-orm", "diesel", "sqlx"] {
+    let config = Config {
+      backend: compiler::backend::from_name(backend),
+      ..Default::default()
+    };
+
+    match compile_str(dbml, &config).unwrap_err() {
+      CompilerError::UnsupportedType { type_name, location } => {
+        assert_eq!(type_name, "integer[3][3]");
+        assert_eq!(location.line_col, Some((4, 3)));
+      }
+      err => panic!("unexpected error: {}", err),
+    }
+  }
+
+  let annotated_dbml = dbml.replace(r#""integer[3][3]""#, r#""integer[3][3]" [note: '@rust_type: Vec<Vec<i32>>']"#);
+  let output = compile_output(&annotated_dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err));
+  let out = output.code().unwrap_or_default();
+
+  assert!(output.warnings.is_empty());
+  assert_entity_field(
+    out,
+    "t",
+    "tags",
+    "column_type = \"Array(sea_orm::sea_query::SeaRc::new(ColumnType::String(Some(16))))\"",
+    "Vec<String>",
+  );
+  assert_entity_field(
+    out,
+    "t",
+    "grid",
+    "column_type = \"Array(sea_orm::sea_query::SeaRc::new(ColumnType::Integer))\"",
+    "Vec<Vec<i32>>",
+  );
+
+  let config = Config {
+    sea_orm_version: SeaOrmVersion::V1,
+    ..Default::default()
+  };
+  let out = compile_str(&annotated_dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains(
+    "#[sea_orm(column_type = \"Array(sea_orm::sea_query::RcOrArc::new(ColumnType::String(StringLen::N(16))))\")]"
+  ));
+
+  // the dimensions and their sizes are kept by the DDL
+  let config = Config {
+    backend: compiler::backend::from_name("sql"),
+    ..Default::default()
+  };
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("\t\"grid\" integer[3][3] NOT NULL\n"));
+
+  let config = Config {
+    backend: compiler::backend::from_name("sea-orm-migration"),
+    ..Default::default()
+  };
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains(".col(ColumnDef::new(T::Tags).array(ColumnType::String(Some(16))).not_null())\n"));
+  assert!(out.contains(".col(ColumnDef::new(T::Grid).custom(Alias::new(\"integer[3][3]\")).not_null())\n"));
+
+  let config = Config {
+    target: Some(Target::MySql),
+    ..Default::default()
+  };
+
+  match compile_str(dbml, &config).unwrap_err() {
+    CompilerError::UnsupportedFeature { feature, location, .. } => {
+      assert_eq!(feature, "array column type");
+      assert_eq!(location.line_col, Some((3, 3)));
+    }
+    err => panic!("unexpected error: {}", err),
+  }
+}
+
+#[test]
+fn compile_type_overrides() {
+  use compiler::config::ConfigFile;
+
+  let dbml = r#"Table t {
+  id int [pk]
+  name citext
+  path "ltree[]"
+  email varchar(320)
+  title varchar(64)
+}"#;
+
+  let file = ConfigFile::parse(
+    r#"
+      [types]
+      citext = { rust_type = "String", column_type = 'custom("citext")' }
+      ltree = { rust_type = "String" }
+      "varchar(320)" = { rust_type = "crate::Email", attributes = ['select_as = "text"'] }
+      int = { rust_type = "crate::Id" }
+    "#,
+  )
+  .unwrap_or_else(|err| panic!("{}", err));
+  let config = file.apply(Config::default()).unwrap_or_else(|err| panic!("{}", err));
+
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert_entity_field(&out, "t", "id", "column_type = \"Integer\", primary_key, auto_increment = false", "crate::Id");
+  assert_entity_field(&out, "t", "name", "column_type = \"custom(\\\"citext\\\")\"", "String");
+  assert_entity_field(&out, "t", "path", "", "Vec<String>");
+  assert_entity_field(&out, "t", "email", "column_type = \"String(Some(320))\", select_as = \"text\"", "crate::Email");
+  assert_entity_field(&out, "t", "title", "column_type = \"String(Some(64))\"", "String");
+
+  let out = compile_str(
+    dbml,
+    &Config {
+      backend: compiler::backend::from_name("sql"),
+      ..config.clone()
+    },
+  )
+  .unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("\t\"name\" citext NOT NULL,\n"));
+  assert!(out.contains("\t\"path\" ltree[] NOT NULL,\n"));
+
+  let out = compile_str(
+    dbml,
+    &Config {
+      backend: compiler::backend::from_name("sea-orm-migration"),
+      ..config
+    },
+  )
+  .unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains(".col(ColumnDef::new(T::Name).custom(Alias::new(\"citext\")).not_null())\n"));
+
+  assert!(matches!(
+    ConfigFile::parse("[types]\ncitext = { column_type = \"Text\" }"),
+    Err(CompilerError::Config { .. })
+  ));
+}
+
+#[test]
+fn compile_sqlite() {
+  let dbml = r#"Project local {
+  database_type: 'SQLite'
+}
+
+Enum status {
+  created
+  done
+}
+
+Table items {
+  id int [pk, increment]
+  name varchar(64)
+  status status
+}"#;
+
+  let out = compile_str(dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("#[sea_orm(table_name = \"items\")]\n"));
+  assert_entity_field(&out, "items", "name", "column_type = \"Text\"", "String");
+  assert!(out.contains(
+    "#[sea_orm(rs_type = \"String\", db_type = \"String(None)\", enum_name = \"status\")]"
+  ));
+
+  let dbml = dbml.replace("name varchar(64)", "tags \"text[]\"");
+
+  match compile_str(&dbml, &Config::default()).unwrap_err() {
+    CompilerError::UnsupportedFeature { location, .. } => {
+      assert_eq!(location.line_col, Some((12, 3)))
+    }
+    err => panic!("unexpected error: {}", err),
+  }
+}
+
+#[test]
+fn custom_backend() {
+  use compiler::{backend::Backend, Output, OutputFile};
+  use sea_orm_dbml::dbml_rs::analyzer::SemanticSchemaBlock;
+
+  #[derive(Debug)]
+  struct TableList;
+
+  impl Backend for TableList {
+    fn name(&self) -> &str {
+      "table-list"
+    }
+
+    fn generate(&self, ast: &SemanticSchemaBlock, _: &Config) -> std::result::Result<Output, CompilerError> {
+      let names: Vec<_> = ast.tables.iter().map(|table| table.ident.name.clone()).collect();
+
+      Ok(Output {
+        files: vec![OutputFile {
+          path: PathBuf::new(),
+          code: names.join("\n"),
+        }],
+        warnings: vec![],
+      })
+    }
+  }
+
+  let dbml = fs::read_to_string(format!("{}/sample_1.dbml", DBML_DIR)).unwrap();
+  let config = Config {
+    backend: Some(std::sync::Arc::new(TableList)),
+    exclude: vec!["orders".to_string()],
+    ..Default::default()
+  };
+
+  assert_eq!(
+    compile_str(&dbml, &config).unwrap_or_else(|err| panic!("{}", err)),
+    "users\nposts"
+  );
+
+  let config = Config {
+    backend: compiler::backend::from_name("sea-orm"),
+    ..Default::default()
+  };
+
+  assert_eq!(
+    compile_str(&dbml, &config).unwrap_or_else(|err| panic!("{}", err)),
+    compile_str(&dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err))
+  );
+}
+
+#[test]
+fn compile_diesel() {
+  use compiler::warn::WarningCode;
+
+  let dbml = fs::read_to_string(format!("{}/sample_1.dbml", DBML_DIR)).unwrap();
+  let config = Config {
+    backend: compiler::backend::from_name("diesel"),
+    ..Default::default()
+  };
+
+  let out = compile_str(&dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("\t\t#[diesel(postgres_type(name = \"post_status\"))]\n\t\tpub struct PostStatus;"));
+  assert!(out.contains("\t\tuse super::sql_types::PostStatus;\n\n\t\tposts (id) {\n"));
+  assert!(out.contains("\t\t\treferral_id -> Nullable<Integer>,\n"));
+  assert!(out.contains("\tdiesel::joinable!(posts -> users (user_id));\n"));
+  assert!(out.contains("\tdiesel::allow_tables_to_appear_in_same_query!(users, posts, orders);\n"));
+  assert!(out.contains("#[diesel(table_name = super::schema::posts)]\n\t#[diesel(check_for_backend(diesel::pg::Pg))]\n\tpub struct Post {"));
+  assert!(out.contains("pub created_at: chrono::NaiveDateTime,"));
+  assert!(out.contains("pub referral_id: Option<i32>,"));
+  assert!(out.contains("\t\t\t\t\"published\" => Ok(Self::Published),\n"));
+
+  let dbml = dbml.replace("Table orders {\n  id integer [pk]", "Table orders {\n  id integer");
+  let output = compile_output(&dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+  let out = output.code().unwrap();
+
+  assert!(output.warnings.iter().any(|warning| warning.code == WarningCode::MissingPrimaryKey));
+  assert!(!out.contains("orders"));
+  assert!(out.contains("diesel::allow_tables_to_appear_in_same_query!(users, posts);"));
+
+  // the checked-in output is compiled against Diesel, so it must stay up to date
+  let dbml = fs::read_to_string(format!("{}/schema.dbml", VERSIONS_DIR)).unwrap();
+  let out = compile_str(&dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+  let expected = fs::read_to_string(format!("{}/diesel.rs", VERSIONS_DIR)).unwrap();
+
+  assert_eq!(out, expected, "diesel.rs is out of date");
+}
+
+#[test]
+fn compile_sqlx() {
+  let dbml = fs::read_to_string(format!("{}/sample_1.dbml", DBML_DIR)).unwrap();
+  let config = Config {
+    backend: compiler::backend::from_name("sqlx"),
+    ..Default::default()
+  };
+
+  let out = compile_str(&dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("#[derive(Clone, Debug, PartialEq, sqlx::FromRow)]\npub struct User {"));
+  assert!(out.contains("\tpub referral_id: Option<i32>,\n"));
+  assert!(out.contains("\tpub status: PostStatus,\n\tpub created_at: chrono::NaiveDateTime,\n"));
+  assert!(out.contains("#[sqlx(type_name = \"post_status\")]\npub enum PostStatus {\n\t#[sqlx(rename = \"draft\")]\n\tDraft,"));
+
+  let config = Config {
+    enum_type: compiler::config::EnumType::Integer,
+    output_mode: compiler::config::OutputMode::Directory,
+    ..config
+  };
+
+  let output = compile_output(&dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+  let file = |name: &str| output.files.iter().find(|file| file.path == Path::new(name)).unwrap();
+
+  assert!(file("posts.rs").code.contains("\tpub status: super::enums::PostStatus,\n"));
+  assert!(file("enums.rs").code.contains("#[repr(i32)]\npub enum PostStatus {\n\tDraft = 0,"));
+
+  // the checked-in outputs are compiled against SQLx, so they must stay up to date
+  let dbml = fs::read_to_string(format!("{}/sqlx.dbml", VERSIONS_DIR)).unwrap();
+
+  for (enum_type, file_name) in [("string", "sqlx.rs"), ("integer", "sqlx_integer_enum.rs")] {
+    let config = Config {
+      backend: compiler::backend::from_name("sqlx"),
+      enum_type: enum_type.parse().unwrap(),
+      ..Default::default()
+    };
+    let out = compile_str(&dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+    let expected = fs::read_to_string(format!("{}/{}", VERSIONS_DIR, file_name)).unwrap();
+
+    assert_eq!(out, expected, "{} is out of date", file_name);
+  }
+}
+
+#[test]
+fn compile_migration() {
+  let config = Config {
+    backend: compiler::backend::from_name("sea-orm-migration"),
+    ..Default::default()
+  };
+
+  let dbml = fs::read_to_string(format!("{}/sample_1.dbml", DBML_DIR)).unwrap();
+  let out = compile_str(&dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("\t\t\t\t\t.as_enum(PostStatus::Table)\n\t\t\t\t\t.values([PostStatus::Draft, PostStatus::Published, PostStatus::Private])\n"));
+  assert!(out.contains(".col(ColumnDef::new(Users::Age).integer().not_null().default(10))\n"));
+  assert!(out.contains(".col(ColumnDef::new(Users::ReferralId).integer())\n"));
+  assert!(out.contains(".col(ColumnDef::new(Posts::Status).enumeration(PostStatus::Table, [PostStatus::Draft, PostStatus::Published, PostStatus::Private]).not_null())\n"));
+  assert!(out.contains("\t\t\t\t\t\t\t.from(Posts::Table, Posts::UserId)\n\t\t\t\t\t\t\t.to(Users::Table, Users::Id),\n"));
+  assert!(out.find(".drop_table(").unwrap() < out.find(".drop_type(").unwrap());
+
+  let dbml = r#"Table a {
+  id int [pk, increment]
+  b_id int [null]
+  "Name" varchar(32)
+
+  indexes {
+    (b_id, "Name") [unique]
+  }
+}
+
+Table b {
+  id int [pk]
+  a_id int
+}
+
+Table c {
+  x int
+  y int
+
+  indexes {
+    (x, y) [pk]
+  }
+}
+
+Ref: a.b_id > b.id
+Ref: b.a_id > a.id [delete: cascade]"#;
+
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains(".col(ColumnDef::new(A::Id).integer().not_null().auto_increment().primary_key())\n"));
+  assert!(out.contains(".primary_key(Index::create().col(C::X).col(C::Y))\n"));
+  assert!(out.contains("\t\t\t\t\t\t\t.to(A::Table, A::Id)\n\t\t\t\t\t\t\t.on_delete(ForeignKeyAction::Cascade),\n"));
+  assert!(out.contains("\t\t\t.create_foreign_key(\n\t\t\t\tForeignKey::create()\n\t\t\t\t\t.name(\"fk_a_b_id\")\n"));
+  assert!(out.contains("\t\t\t\t\t.name(\"idx_a_b_id_Name\")\n\t\t\t\t\t.table(A::Table)\n\t\t\t\t\t.col(A::BId)\n\t\t\t\t\t.col(A::Name)\n\t\t\t\t\t.unique()\n"));
+  assert!(out.contains("\t#[iden = \"Name\"]\n\tName,\n"));
+  assert!(out.find(".drop_foreign_key(").unwrap() < out.find(".drop_table(").unwrap());
+
+  // the refs are matched to the tables by schema, so the table of the same name in another schema is not taken
+  let dbml = r#"Table archive.b {
+  id int [pk]
+}
+
+Table a {
+  id int [pk]
+  b_id int
+}
+
+Table b {
+  id int [pk]
+  a_id int
+}
+
+Ref: a.b_id > b.id
+Ref: b.a_id > a.id"#;
+
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("\t\t\t.create_foreign_key(\n\t\t\t\tForeignKey::create()\n\t\t\t\t\t.name(\"fk_a_b_id\")\n"));
+}
+
+#[test]
+fn compile_sea_orm_versions() {
+  use compiler::config::SeaOrmVersion;
+
+  let dbml = r#"Enum status {
+  created
+  done
+}
+
+Table items {
+  id int [pk, increment]
+  "Name" varchar(64)
+  status status
+}"#;
+
+  let out = compile_str(dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err));
+
+  assert_entity_field(&out, "items", "Name", "column_type = \"String(Some(64))\"", "String");
+  assert!(out.contains("#[derive(Copy, Clone, Debug, EnumIter)]\n\tpub enum Relation {}\n\n\timpl RelationTrait for Relation {"));
+  assert!(out.contains("enum_name = \"status\", schema_name = \"public\")]"));
+
+  let config = Config {
+    sea_orm_version: "1.0".parse().unwrap(),
+    enum_type: "string(8)".parse().unwrap(),
+    is_native_enum: false,
+    ..Default::default()
+  };
+  assert_eq!(config.sea_orm_version, SeaOrmVersion::V1);
+
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert_entity_field(&out, "items", "Name", "column_type = \"String(StringLen::N(64))\"", "String");
+  assert!(out.contains("#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]\n\tpub enum Relation {}\n"));
+  assert!(out.contains(
+    "#[sea_orm(rs_type = \"String\", db_type = \"String(StringLen::N(8))\", enum_name = \"status\")]"
+  ));
+
+  let config = Config {
+    backend: compiler::backend::from_name("sea-orm-migration"),
+    ..Default::default()
+  };
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+  assert!(out.contains("#[derive(Iden)]\nenum Items {\n\tTable,\n\tId,\n\t#[iden = \"Name\"]\n\tName,\n"));
+
+  let config = Config {
+    sea_orm_version: SeaOrmVersion::V0_12,
+    ..config
+  };
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+  assert!(out.contains("#[derive(DeriveIden)]\nenum Items {\n\tTable,\n\tId,\n\t#[sea_orm(iden = \"Name\")]\n\tName,\n"));
+
+  assert!("0.10".parse::<SeaOrmVersion>().is_err());
+}
+
+#[test]
+fn compile_sql() {
+  use compiler::config::Target;
+
+  let config = Config {
+    backend: compiler::backend::from_name("sql"),
+    ..Default::default()
+  };
+
+  let dbml = fs::read_to_string(format!("{}/index_tables.in.dbml", DBML_DIR)).unwrap();
+  let out = compile_str(&dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("CREATE TABLE \"users\" (\n\t\"id\" integer NOT NULL PRIMARY KEY,\n"));
+  assert!(out.contains("\t\"email\" varchar NOT NULL UNIQUE,\n"));
+  assert!(out.contains("CREATE INDEX \"User Name\" ON \"users\" (\"full_name\");"));
+  assert!(out.contains("CREATE INDEX \"idx_users_email_created_at\" ON \"users\" USING hash (\"email\", \"created_at\");"));
+  assert!(out.contains("CREATE INDEX \"idx_users_active_lower_full_name\" ON \"users\" (\"active\", (lower(full_name)));"));
+
+  let dbml = r#"Enum status {
+  active
+  "on hold"
+}
+
+Table a {
+  id int [pk, increment]
+  b_id int [null]
+  status status [default: 'active']
+  created_at timestamp [default: `now()`]
+}
+
+Table b {
+  id int [pk]
+  a_id int
+}
+
+Ref: a.b_id > b.id
+Ref: b.a_id > a.id [delete: cascade]"#;
+
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("CREATE TYPE \"status\" AS ENUM ('active', 'on hold');\n\nCREATE TABLE \"a\" (\n\t\"id\" serial NOT NULL PRIMARY KEY,\n"));
+  assert!(out.contains("\t\"status\" \"status\" NOT NULL DEFAULT 'active',\n\t\"created_at\" timestamp NOT NULL DEFAULT now()\n"));
+  assert!(out.contains("\tCONSTRAINT \"fk_b_a_id\" FOREIGN KEY (\"a_id\") REFERENCES \"a\" (\"id\") ON DELETE CASCADE\n);"));
+  assert!(out.ends_with("ALTER TABLE \"a\" ADD CONSTRAINT \"fk_a_b_id\" FOREIGN KEY (\"b_id\") REFERENCES \"b\" (\"id\");\n"));
+
+  // the refs are matched to the tables by schema, so the table of the same name in another schema is not taken
+  let schema_dbml = r#"Table archive.b {
+  id int [pk]
+}
+
+Table a {
+  id int [pk]
+  b_id int
+}
+
+Table b {
+  id int [pk]
+  a_id int
+}
+
+Ref: a.b_id > b.id
+Ref: b.a_id > a.id"#;
+
+  let out = compile_str(schema_dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.ends_with("ALTER TABLE \"a\" ADD CONSTRAINT \"fk_a_b_id\" FOREIGN KEY (\"b_id\") REFERENCES \"b\" (\"id\");\n"));
+
+  let mysql_config = Config {
+    target: Some(Target::MySql),
+    ..config.clone()
+  };
+  let out = compile_str(dbml, &mysql_config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("\t`id` int NOT NULL PRIMARY KEY AUTO_INCREMENT,\n"));
+  assert!(out.contains("\t`status` enum('active', 'on hold') NOT NULL DEFAULT 'active',\n\t`created_at` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP\n"));
+
+  let sqlite_config = Config {
+    target: Some(Target::Sqlite),
+    ..config
+  };
+  let out = compile_str(dbml, &sqlite_config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("\t\"id\" integer NOT NULL PRIMARY KEY AUTOINCREMENT,\n"));
+  assert!(out.contains("\tCONSTRAINT \"fk_a_b_id\" FOREIGN KEY (\"b_id\") REFERENCES \"b\" (\"id\")\n);"));
+  assert!(!out.contains("ALTER TABLE"));
+}
+
+#[test]
+fn compile_schema_diff() {
+  use compiler::config::Target;
+  use compiler::diff::{self, Change};
+
+  let old_dbml = r#"Enum status {
+  active
+}
+
+Table users {
+  id int [pk, increment]
+  name varchar(64)
+  age int [default: 0]
+  status status
+}
+
+Table legacy {
+  id int [pk]
+}"#;
+  let new_dbml = r#"Enum status {
+  active
+  banned
+}
+
+Table users {
+  id int [pk, increment]
+  name varchar(128) [null]
+  status status
+  team_id int
+
+  indexes {
+    team_id
+  }
+}
+
+Table teams {
+  id int [pk, increment]
+}
+
+Ref: users.team_id > teams.id [delete: cascade]"#;
+
+  let old = compiler::parse(old_dbml).unwrap();
+  let new = compiler::parse(new_dbml).unwrap();
+  let kinds: Vec<_> = diff::diff(&old, &new)
+    .changes
+    .iter()
+    .map(|change| match change {
+      Change::AddEnumValue { value, .. } => format!("add value {}", value),
+      Change::DropTable(table) => format!("drop table {}", table.ident.name),
+      Change::CreateTable(table) => format!("create table {}", table.ident.name),
+      Change::AddColumn { column, .. } => format!("add column {}", column.name),
+      Change::AlterColumn { new, .. } => format!("alter column {}", new.name),
+      Change::DropColumn { column, .. } => format!("drop column {}", column.name),
+      Change::AddRef(ref_block) => format!("add ref {}", ref_block.lhs.compositions.join(", ")),
+      Change::AddIndex { index, .. } => format!("add index {}", index.cols.len()),
+      change => panic!("unexpected change: {:?}", change),
+    })
+    .collect();
+
+  assert_eq!(
+    kinds,
+    [
+      "add value banned",
+      "drop table legacy",
+      "create table teams",
+      "alter column name",
+      "add column team_id",
+      "drop column age",
+      "add ref team_id",
+      "add index 1",
+    ]
+  );
+  assert!(diff::diff(&new, &new).is_empty());
+
+  let config = Config {
+    backend: compiler::backend::from_name("sql"),
+    ..Default::default()
+  };
+  let out = compile_diff(old_dbml, new_dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+  let out = out.code().unwrap();
+
+  assert!(out.contains("ALTER TYPE \"status\" ADD VALUE 'banned';\n\nDROP TABLE \"legacy\";\n\nCREATE TABLE \"teams\" (\n"));
+  assert!(out.contains("ALTER TABLE \"users\" ALTER COLUMN \"name\" TYPE varchar(128);\n\nALTER TABLE \"users\" ALTER COLUMN \"name\" DROP NOT NULL;\n"));
+  assert!(out.contains("ALTER TABLE \"users\" DROP COLUMN \"age\";\n"));
+  assert!(out.contains("ALTER TABLE \"users\" ADD CONSTRAINT \"fk_users_team_id\" FOREIGN KEY (\"team_id\") REFERENCES \"teams\" (\"id\") ON DELETE CASCADE;\n"));
+  assert!(out.ends_with("CREATE INDEX \"idx_users_team_id\" ON \"users\" (\"team_id\");\n"));
+
+  let mysql_config = Config {
+    target: Some(Target::MySql),
+    ..config.clone()
+  };
+  let out = compile_diff(old_dbml, new_dbml, &mysql_config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.code().unwrap().contains("ALTER TABLE `users` MODIFY COLUMN `status` enum('active', 'banned') NOT NULL;\n"));
+
+  let sqlite_config = Config {
+    target: Some(Target::Sqlite),
+    ..config
+  };
+
+  match compile_diff(old_dbml, new_dbml, &sqlite_config).unwrap_err() {
+    CompilerError::UnsupportedFeature { location, .. } => assert_eq!(location.line_col, Some((8, 3))),
+    err => panic!("unexpected error: {}", err),
+  }
+
+  let out = compile_diff(old_dbml, new_dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err));
+  let out = out.code().unwrap();
+
+  assert!(out.contains("\t\t\t\tType::alter()\n\t\t\t\t\t.name(Status::Table)\n\t\t\t\t\t.add_value(Status::Banned)\n"));
+  assert!(out.contains("\t\t\t\t\t.modify_column(ColumnDef::new(Users::Name).string_len(128).null())\n"));
+  assert!(out.contains("\t\t\t\t\t.add_column(ColumnDef::new(Users::Age).integer().not_null().default(0))\n"));
+  assert!(out.contains("\t\t// the value 'banned' of enum 'status' is kept, as Postgres does not drop enum values\n"));
+
+  // the keys and the auto-increment are altered as well
+  let old_dbml = r#"Table accounts {
+  id int [pk]
+  email varchar
+  code int [unique]
+  seq int
+}"#;
+  let new_dbml = r#"Table accounts {
+  id int
+  email varchar [unique]
+  code int [pk]
+  seq int [increment]
+}"#;
+
+  let old = compiler::parse(old_dbml).unwrap();
+  let new = compiler::parse(new_dbml).unwrap();
+  let altered: Vec<_> = diff::diff(&old, &new)
+    .changes
+    .iter()
+    .map(|change| match change {
+      Change::AlterColumn { new, .. } => new.name.clone(),
+      change => panic!("unexpected change: {:?}", change),
+    })
+    .collect();
+
+  // the primary key is dropped before it is added to another column
+  assert_eq!(altered, ["id", "email", "code", "seq"]);
+
+  let sql_config = Config {
+    backend: compiler::backend::from_name("sql"),
+    ..Default::default()
+  };
+  let out = compile_diff(old_dbml, new_dbml, &sql_config).unwrap_or_else(|err| panic!("{}", err));
+  let stmts: Vec<_> = out.code().unwrap().lines().filter(|line| !line.is_empty()).skip(1).collect();
+
+  assert_eq!(
+    stmts,
+    [
+      "ALTER TABLE \"accounts\" DROP CONSTRAINT \"accounts_pkey\";",
+      "ALTER TABLE \"accounts\" ADD CONSTRAINT \"accounts_email_key\" UNIQUE (\"email\");",
+      "ALTER TABLE \"accounts\" ADD PRIMARY KEY (\"code\");",
+      "ALTER TABLE \"accounts\" DROP CONSTRAINT \"accounts_code_key\";",
+      "CREATE SEQUENCE \"accounts_seq_seq\" OWNED BY \"accounts\".\"seq\";",
+      "ALTER TABLE \"accounts\" ALTER COLUMN \"seq\" SET DEFAULT nextval('\"accounts_seq_seq\"');",
+    ]
+  );
+
+  let mysql_config = Config {
+    target: Some(Target::MySql),
+    ..sql_config.clone()
+  };
+  let out = compile_diff(old_dbml, new_dbml, &mysql_config).unwrap_or_else(|err| panic!("{}", err));
+  let stmts: Vec<_> = out.code().unwrap().lines().filter(|line| !line.is_empty()).skip(1).collect();
+
+  assert_eq!(
+    stmts,
+    [
+      "ALTER TABLE `accounts` DROP PRIMARY KEY;",
+      "ALTER TABLE `accounts` ADD UNIQUE INDEX `email` (`email`);",
+      "ALTER TABLE `accounts` ADD PRIMARY KEY (`code`);",
+      "ALTER TABLE `accounts` DROP INDEX `code`;",
+      "ALTER TABLE `accounts` MODIFY COLUMN `seq` int NOT NULL AUTO_INCREMENT;",
+    ]
+  );
+
+  let sqlite_config = Config {
+    target: Some(Target::Sqlite),
+    ..sql_config
+  };
+
+  assert!(matches!(
+    compile_diff(old_dbml, new_dbml, &sqlite_config).unwrap_err(),
+    CompilerError::UnsupportedFeature { .. }
+  ));
+
+  let out = compile_diff(old_dbml, new_dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err));
+  let out = out.code().unwrap();
+
+  assert!(out.contains(".execute_unprepared(\"ALTER TABLE \\\"accounts\\\" ADD PRIMARY KEY (\\\"code\\\");\")"));
+  assert!(out.contains(".execute_unprepared(\"DROP SEQUENCE \\\"accounts_seq_seq\\\";\")"));
+  assert!(!out.contains(".modify_column("));
+
+  let config = Config {
+    backend: compiler::backend::from_name("sea-orm"),
+    ..Default::default()
+  };
+
+  assert!(matches!(
+    compile_diff(old_dbml, new_dbml, &config).unwrap_err(),
+    CompilerError::Config { .. }
+  ));
+}
+
+#[test]
+fn build_into_out_dir() -> Result<()> {
+  let out_dir = std::env::temp_dir().join(format!("{}-build-{}", NAME, std::process::id()));
+  fs::create_dir_all(&out_dir)?;
+
+  let out_paths = build::Builder::new()
+    .input(format!("{}/sample_1.dbml", DBML_DIR))
+    .input(format!("{}/sea_orm_bakery.dbml", DBML_DIR))
+    .out_dir(&out_dir)
+    .compile()
+    .unwrap_or_else(|err| panic!("{}", err));
+
+  assert_eq!(
+    out_paths,
+    vec![out_dir.join("sample_1.rs"), out_dir.join("sea_orm_bakery.rs")]
+  );
+
+  let out = fs::read_to_string(&out_paths[0])?;
+  assert!(out.starts_with(&format!("// Generated by {} {}", NAME, VERSION)));
+  assert!(!out.contains("//!"));
+
+  fs::remove_dir_all(&out_dir)
+}
+
+#[test]
+fn cli_commands() -> Result<()> {
+  use std::io::Write;
+  use std::process::{Command, Stdio};
+
+  let bin = env!("CARGO_BIN_EXE_sea-orm-dbml");
+  let in_path = format!("{}/sample_1.dbml", DBML_DIR);
+  let dbml = fs::read_to_string(&in_path)?;
+
+  let mut child = Command::new(bin)
+    .args(["generate", "--target", "postgres", "-"])
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()?;
+  child.stdin.take().unwrap().write_all(dbml.as_bytes())?;
+  let out = child.wait_with_output()?;
+
+  assert!(out.status.success());
+  assert_eq!(
+    String::from_utf8_lossy(&out.stdout),
+    compile_str(&dbml, &Config::default()).unwrap_or_else(|err| panic!("{}", err))
+  );
+
+  let out = Command::new(bin).args(["check", &in_path]).output()?;
+  assert!(out.status.success());
+
+  let out = Command::new(bin).args(["lint", "-i", &in_path]).output()?;
+  assert!(!out.status.success());
+  assert!(String::from_utf8_lossy(&out.stderr).contains("[table-group]"));
+
+  let out = Command::new(bin).args(["generate", "--target", "oracle"]).output()?;
+  assert_eq!(out.status.code(), Some(2));
+
+  Ok(())
+}
+
+#[test]
+fn compile_directory() -> Result<()> {
+  let dbml = fs::read_to_string(format!("{}/sample_1.dbml", DBML_DIR))?;
+  let config = Config {
+    output_mode: compiler::config::OutputMode::Directory,
+    ..Default::default()
+  };
+
+  let output = compile_output(&dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  let paths: Vec<_> = output.files.iter().map(|file| file.path.to_str().unwrap()).collect();
+  assert_eq!(
+    paths,
+    vec![
+      "mod.rs",
+      "prelude.rs",
+      "users.rs",
+      "posts.rs",
+      "orders.rs",
+      "sea_orm_active_enums.rs"
+    ]
+  );
+  assert!(output.files[0].code.contains("pub mod sea_orm_active_enums;"));
+  assert!(output.files[1].code.contains("pub use super::posts::Entity as Posts;"));
+  assert!(output.files[3].code.contains("pub status: super::sea_orm_active_enums::PostStatus,"));
+  // every file starts with the header of the other backends
+  for file in output.files.iter() {
+    assert!(file.code.starts_with(&format!("//! Generated by {} {}\n//!\n//! Description of the project\n\n", NAME, VERSION)));
+  }
+  assert!(output.code().is_none());
+  assert!(compile_str(&dbml, &config).is_err());
+
+  Ok(())
+}
+
+#[test]
+fn check_stale_output() -> Result<()> {
+  let out_path = std::env::temp_dir().join(format!("{}-check-{}.rs", NAME, std::process::id()));
+  let in_path = format!("{}/sea_orm_bakery.dbml", DBML_DIR);
+  let config = || Config {
+    in_path: OsString::from(&in_path),
+    out_path: OsString::from(&out_path),
+    ..Default::default()
+  };
+
+  let diffs = check(config()).unwrap_or_else(|err| panic!("{}", err));
+  assert_eq!(diffs.len(), 1);
+
+  compile(config()).unwrap_or_else(|err| panic!("{}", err));
+  assert!(check(config()).unwrap_or_else(|err| panic!("{}", err)).is_empty());
+
+  let stale = fs::read_to_string(&out_path)?.replace("pub profit_margin: f64,", "pub margin: f64,");
+  fs::write(&out_path, stale)?;
+
+  let diffs = check(config()).unwrap_or_else(|err| panic!("{}", err));
+  assert_eq!(diffs.len(), 1);
+  assert!(diffs[0].contains("@@ -11,7 +11,7 @@\n"));
+  assert!(diffs[0].contains("\n-\t\tpub margin: f64,\n+\t\tpub profit_margin: f64,\n"));
+
+  let out = std::process::Command::new(env!("CARGO_BIN_EXE_sea-orm-dbml"))
+    .args(["check", &in_path, "-o", out_path.to_str().unwrap()])
+    .output()?;
+  assert!(!out.status.success());
+  assert_eq!(String::from_utf8_lossy(&out.stdout), diffs[0]);
+
+  fs::remove_file(&out_path)
+}
+
+#[test]
+fn config_file() -> Result<()> {
+  use compiler::config::{ConfigFile, EnumType, OutputMode};
+
+  let file = ConfigFile::parse(
+    r#"
+      enum_type = "string(16)"
+      native_enum = false
+      exclude = ["p*"]
+
+      [[input]]
+      path = "sample_1.dbml"
+      output = "../out/sample_1.rs"
+
+      [[input]]
+      path = "sea_orm_bakery.dbml"
+    "#,
+  )
+  .unwrap_or_else(|err| panic!("{}", err));
+
+  let configs = file.configs(DBML_DIR).unwrap_or_else(|err| panic!("{}", err));
+  assert_eq!(configs.len(), 2);
+  assert_eq!(configs[0].in_path, OsString::from("tests/dbml/sample_1.dbml"));
+  assert_eq!(configs[0].out_path, OsString::from("tests/dbml/../out/sample_1.rs"));
+  assert!(configs[1].out_path.is_empty());
+  assert_eq!(configs[0].enum_type, EnumType::String(Some(16)));
+  assert_eq!(configs[0].output_mode, OutputMode::File);
+  assert!(!configs[0].is_native_enum);
+
+  let dbml = fs::read_to_string(&configs[0].in_path)?;
+  let out = compile_str(&dbml, &configs[0]).unwrap_or_else(|err| panic!("{}", err));
+  assert!(out.contains("pub mod users {"));
+  assert!(!out.contains("pub mod posts {"));
+  assert!(!out.contains("super::posts::Entity"));
+
+  assert!(matches!(
+    ConfigFile::parse("target = \"oracle\"").and_then(|file| file.apply(Config::default())),
+    Err(CompilerError::Config { .. })
+  ));
+  assert!(matches!(ConfigFile::parse("naming = 1"), Err(CompilerError::Config { .. })));
+
+  let config_path = std::env::temp_dir().join(format!("{}-{}.toml", NAME, std::process::id()));
+  fs::write(
+    &config_path,
+    format!(
+      "exclude = [\"orders\"]\n\n[[input]]\npath = \"{}\"\n",
+      fs::canonicalize(&configs[0].in_path)?.display()
+    ),
+  )?;
+
+  let out = std::process::Command::new(env!("CARGO_BIN_EXE_sea-orm-dbml"))
+    .args(["generate", "--config", config_path.to_str().unwrap()])
+    .output()?;
+  assert!(out.status.success());
+  assert!(!String::from_utf8_lossy(&out.stdout).contains("pub mod orders {"));
+
+  fs::remove_file(&config_path)
+}
+
+#[test]
+fn compile_naming() {
+  use compiler::config::{ConfigFile, Naming};
+
+  let dbml = r#"Table tbl_users {
+  id int [pk]
+}
+
+Table tbl_posts {
+  id int [pk]
+  user_id int
+}
+
+Ref: tbl_posts.user_id > tbl_users.id"#;
+
+  let file = ConfigFile::parse("[naming]\nstrip_prefixes = [\"tbl_\"]\nsingular = true").unwrap();
+  let config = file.apply(Config::default()).unwrap();
+  assert_eq!(
+    config.naming,
+    Naming {
+      strip_prefixes: vec!["tbl_".to_string()],
+      is_singular: true,
+    }
+  );
+
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("pub mod user {"));
+  assert!(out.contains("pub mod post {"));
+  assert!(out.contains(r#"#[sea_orm(table_name = "tbl_users", schema_name = "public")]"#));
+  assert!(out.contains(r#"belongs_to = "super::user::Entity""#));
+  assert!(out.contains("impl Related<super::post::Entity> for Entity {"));
+  assert!(out.contains("User,"));
+
+  let config = Config {
+    backend: compiler::backend::from_name("sqlx"),
+    ..config
+  };
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("pub struct User {"));
+
+  let config = Config {
+    backend: compiler::backend::from_name("diesel"),
+    ..config
+  };
+  let out = compile_str(dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+
+  assert!(out.contains("\t\ttbl_users (id) {\n"));
+  assert!(out.contains("#[diesel(table_name = super::schema::tbl_users)]"));
+  assert!(out.contains("pub struct User {"));
+  assert!(out.contains("pub struct Post {"));
+
+  assert!(matches!(
+    ConfigFile::parse("[naming]\nsingular = \"yes\""),
+    Err(CompilerError::Config { .. })
+  ));
+}
+
+#[test]
+fn compile_sea_orm_version_entities() {
+  use compiler::config::SeaOrmVersion;
+
+  let dbml = fs::read_to_string(format!("{}/schema.dbml", VERSIONS_DIR)).unwrap();
+
+  // the checked-in entities are compiled against their SeaORM release, so they must stay up to date
+  for (version, file_name) in [
+    (SeaOrmVersion::V0_11, "v0_11.rs"),
+    (SeaOrmVersion::V0_12, "v0_12.rs"),
+    (SeaOrmVersion::V1, "v1.rs"),
+  ] {
+    let config = Config {
+      sea_orm_version: version,
+      ..Default::default()
+    };
+    let output = compile_output(&dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+    let expected = fs::read_to_string(format!("{}/{}", VERSIONS_DIR, file_name)).unwrap();
+
+    assert_eq!(output.code().unwrap(), expected, "{} is out of date", file_name);
+
+    let has_default_expr_warnings = output.warnings.iter().any(|warning| warning.code == WarningCode::DefaultExpr);
+    assert_eq!(has_default_expr_warnings, version == SeaOrmVersion::V0_11);
+  }
+}
+
+#[test]
+fn compile_sea_orm_version_migrations() {
+  use compiler::config::SeaOrmVersion;
+  use sea_orm_migration::MigrationName;
+
+  let old_dbml = fs::read_to_string(format!("{}/schema_old.dbml", VERSIONS_DIR)).unwrap();
+  let dbml = fs::read_to_string(format!("{}/schema.dbml", VERSIONS_DIR)).unwrap();
+
+  // the checked-in migrations are compiled against their SeaORM release, so they must stay up to date
+  for (version, file_name) in [
+    (SeaOrmVersion::V0_11, "migration_v0_11.rs"),
+    (SeaOrmVersion::V0_12, "migration_v0_12.rs"),
+    (SeaOrmVersion::V1, "migration_v1.rs"),
+  ] {
+    let config = Config {
+      backend: compiler::backend::from_name("sea-orm-migration"),
+      sea_orm_version: version,
+      ..Default::default()
+    };
+    let out = compile_str(&dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+    let expected = fs::read_to_string(format!("{}/{}", VERSIONS_DIR, file_name)).unwrap();
+
+    assert_eq!(out, expected, "{} is out of date", file_name);
+  }
+
+  let config = Config {
+    backend: compiler::backend::from_name("sea-orm-migration"),
+    ..Default::default()
+  };
+  let output = compile_diff(&old_dbml, &dbml, &config).unwrap_or_else(|err| panic!("{}", err));
+  let expected = fs::read_to_string(format!("{}/migration_diff_v0_11.rs", VERSIONS_DIR)).unwrap();
+
+  assert_eq!(output.code().unwrap(), expected, "migration_diff_v0_11.rs is out of date");
+
+  // the migrations are named after their files
+  assert_eq!(sea_orm_0_11_migration::Migration.name(), "migration_v0_11");
+  assert_eq!(sea_orm_0_11_migration_diff::Migration.name(), "migration_diff_v0_11");
+}
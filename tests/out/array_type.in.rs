@@ -1,37 +0,0 @@
-//! Generated by sea-orm-dbml 0.1.0-beta.2
-
-pub mod sal_emp {
-	use sea_orm::entity::prelude::*;
-
-	#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
-	#[sea_orm(table_name = "sal_emp", schema_name = "public")]
-	pub struct Model {
-		#[sea_orm(column_type = "Text")]
-		pub name: String,
-		#[sea_orm(column_type = "Array<Arc<Integer>>")]
-		pub pay_by_quarter: Vec<i32>,
-		#[sea_orm(column_type = "Array<Arc<Array<Arc<Text>>>>")]
-		pub schedule: Vec<Vec<String>>,
-	}
-
-	#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-	pub enum Relation {}
-
-	impl ActiveModelBehavior for ActiveModel {}
-}
-
-pub mod tictactoe {
-	use sea_orm::entity::prelude::*;
-
-	#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
-	#[sea_orm(table_name = "tictactoe", schema_name = "public")]
-	pub struct Model {
-		#[sea_orm(column_type = "Array<Arc<Array<Arc<Integer>>>>")]
-		pub squares: Vec<Vec<i32>>,
-	}
-
-	#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-	pub enum Relation {}
-
-	impl ActiveModelBehavior for ActiveModel {}
-}
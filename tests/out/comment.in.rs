@@ -18,9 +18,15 @@ pub mod orders {
 		pub created_at: String,
 	}
 
-	#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+	#[derive(Copy, Clone, Debug, EnumIter)]
 	pub enum Relation {}
 
+	impl RelationTrait for Relation {
+		fn def(&self) -> RelationDef {
+			panic!("No RelationDef")
+		}
+	}
+
 	impl ActiveModelBehavior for ActiveModel {}
 }
 
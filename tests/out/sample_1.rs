@@ -1,4 +1,6 @@
 //! Generated by sea-orm-dbml 0.1.0-beta.2
+//!
+//! Description of the project
 
 use sea_orm::entity::prelude::*;
 
@@ -96,9 +98,15 @@ pub mod orders {
 		pub status: String,
 	}
 
-	#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+	#[derive(Copy, Clone, Debug, EnumIter)]
 	pub enum Relation {}
 
+	impl RelationTrait for Relation {
+		fn def(&self) -> RelationDef {
+			panic!("No RelationDef")
+		}
+	}
+
 	impl ActiveModelBehavior for ActiveModel {}
 }
 
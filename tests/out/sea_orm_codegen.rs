@@ -134,9 +134,9 @@ pub mod cake_filling {
 	#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 	#[sea_orm(table_name = "cake_filling", schema_name = "public")]
 	pub struct Model {
-		#[sea_orm(column_type = "Integer", primary_key)]
+		#[sea_orm(column_type = "Integer", primary_key, auto_increment = false)]
 		pub cake_id: i32,
-		#[sea_orm(column_type = "Integer", primary_key)]
+		#[sea_orm(column_type = "Integer", primary_key, auto_increment = false)]
 		pub filling_id: i32,
 	}
 
@@ -0,0 +1,17 @@
+//! Compiles the entities and the migration generated for SeaORM 0.12.
+
+extern crate sea_orm_0_12 as sea_orm;
+extern crate sea_orm_migration_0_12 as sea_orm_migration;
+
+#[path = "versions/v0_12.rs"]
+mod entities;
+
+#[path = "versions/migration_v0_12.rs"]
+mod migration;
+
+#[test]
+fn migration_name() {
+  use sea_orm_migration::MigrationName;
+
+  assert_eq!(migration::Migration.name(), "migration_v0_12");
+}
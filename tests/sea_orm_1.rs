@@ -0,0 +1,17 @@
+//! Compiles the entities and the migration generated for SeaORM 1.
+
+extern crate sea_orm_1 as sea_orm;
+extern crate sea_orm_migration_1 as sea_orm_migration;
+
+#[path = "versions/v1.rs"]
+mod entities;
+
+#[path = "versions/migration_v1.rs"]
+mod migration;
+
+#[test]
+fn migration_name() {
+  use sea_orm_migration::MigrationName;
+
+  assert_eq!(migration::Migration.name(), "migration_v1");
+}
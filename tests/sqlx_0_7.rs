@@ -0,0 +1,68 @@
+//! Compiles the structs and the enums generated for SQLx 0.7.
+
+#[path = "versions/sqlx.rs"]
+mod native_enum;
+
+#[path = "versions/sqlx_integer_enum.rs"]
+mod integer_enum;
+
+#[test]
+fn enum_type_info() {
+  use sqlx::postgres::{PgTypeInfo, Postgres};
+  use sqlx::Type;
+
+  assert_eq!(
+    <native_enum::InvoiceStatus as Type<Postgres>>::type_info(),
+    PgTypeInfo::with_name("invoice_status")
+  );
+  assert_eq!(
+    <integer_enum::InvoiceStatus as Type<Postgres>>::type_info(),
+    <i32 as Type<Postgres>>::type_info()
+  );
+}
+
+/// Builds the rows of a module, with the types of the fields spelled out.
+macro_rules! rows {
+  ($module:ident) => {
+    (
+      $module::Customer {
+        id: uuid::Uuid::nil(),
+        name: "Acme".to_string(),
+        tags: Some(vec!["wholesale".to_string()]),
+        created_at: chrono::DateTime::<chrono::FixedOffset>::default(),
+      },
+      $module::Invoice {
+        id: 1,
+        customer_id: uuid::Uuid::nil(),
+        status: $module::InvoiceStatus::Sent,
+        amount: sqlx::postgres::types::PgMoney(1050),
+        total: rust_decimal::Decimal::new(1050, 2),
+        due_in: Some(sqlx::postgres::types::PgInterval {
+          months: 1,
+          days: 0,
+          microseconds: 0,
+        }),
+        send_at: Some(sqlx::postgres::types::PgTimeTz {
+          time: chrono::NaiveTime::default(),
+          offset: chrono::FixedOffset::east_opt(0).unwrap(),
+        }),
+        issued_on: chrono::NaiveDate::default(),
+        metadata: Some(serde_json::json!({ "source": "import" })),
+      },
+    )
+  };
+}
+
+fn assert_from_row<T: for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow>>(_: &T) {}
+
+#[test]
+fn from_row() {
+  let (customer, invoice) = rows!(native_enum);
+  assert_from_row(&customer);
+  assert_from_row(&invoice);
+
+  let (customer, invoice) = rows!(integer_enum);
+  assert_from_row(&customer);
+  assert_from_row(&invoice);
+  assert_eq!(invoice.status as i32, 1);
+}